@@ -0,0 +1,136 @@
+/// Memory a phase allocated, as seen by the counting allocator
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Allocations {
+    pub count: u64,
+    /// The most bytes the phase had allocated at once, on top of what was live when it started
+    pub peak_bytes: usize,
+}
+
+/// Runs `phase`, counting what it allocates when the runner was built with the
+/// `count-allocations` feature
+#[cfg(not(feature = "count-allocations"))]
+pub fn measure<T>(phase: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    (phase(), None)
+}
+
+#[cfg(feature = "count-allocations")]
+pub use counting::measure;
+
+// Counters are kept per thread, so that days solved in parallel are measured apart. Memory freed
+// on another thread than it was allocated on is counted against the thread freeing it.
+#[cfg(feature = "count-allocations")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    use super::Allocations;
+
+    #[derive(Clone, Copy)]
+    struct Counters {
+        count: u64,
+        live: isize,
+        peak: isize,
+    }
+
+    thread_local! {
+        // Initialised without allocating and without a destructor, so the allocator can use it
+        static COUNTERS: Cell<Counters> = const {
+            Cell::new(Counters {
+                count: 0,
+                live: 0,
+                peak: 0,
+            })
+        };
+    }
+
+    fn update(counters: impl FnOnce(&mut Counters)) {
+        // Only fails while the thread is being torn down, when there is nothing left to measure
+        let _ = COUNTERS.try_with(|cell| {
+            let mut value = cell.get();
+            counters(&mut value);
+            cell.set(value);
+        });
+    }
+
+    fn allocated(bytes: usize) {
+        update(|counters| {
+            counters.count += 1;
+            counters.live += bytes as isize;
+            counters.peak = counters.peak.max(counters.live);
+        });
+    }
+
+    fn freed(bytes: usize) {
+        update(|counters| counters.live -= bytes as isize);
+    }
+
+    struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            freed(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                freed(layout.size());
+                allocated(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    pub fn measure<T>(phase: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+        let mut start = None;
+        update(|counters| {
+            counters.peak = counters.live;
+            start = Some(*counters);
+        });
+        let output = phase();
+        let end = COUNTERS.with(Cell::get);
+        let start = start.unwrap_or(end);
+        let allocations = Allocations {
+            count: end.count - start.count,
+            peak_bytes: (end.peak - start.live).max(0) as usize,
+        };
+        (output, Some(allocations))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::alloc::{measure, Allocations};
+
+        #[test]
+        fn measure_test() {
+            let (_, allocations) = measure(|| {
+                let small = vec![0u8; 100];
+                drop(small);
+                vec![0u8; 4000]
+            });
+            assert_eq!(
+                Some(Allocations {
+                    count: 2,
+                    peak_bytes: 4000
+                }),
+                allocations
+            );
+            let (_, allocations) = measure(|| 1 + 1);
+            assert_eq!(Some(Allocations::default()), allocations);
+        }
+    }
+}
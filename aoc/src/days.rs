@@ -0,0 +1,204 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::{eyre, Result};
+use common::{generate::Generator, Solution};
+use day1::Day1;
+use day2::Day2;
+use day3::Day3;
+use day4::Day4;
+use day5::Day5;
+use day6::Day6;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::alloc::{self, Allocations};
+
+/// The steps solving a day is made up of, in the order they run
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part 1",
+            Phase::Part2 => "part 2",
+        };
+        f.pad(name)
+    }
+}
+
+/// How one phase of solving a day went
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PhaseRun {
+    pub phase: Phase,
+    pub duration: Duration,
+    /// Only counted when the runner is built with the `count-allocations` feature
+    pub allocations: Option<Allocations>,
+    /// The answer for a part, nothing for parsing, or the error the phase failed with
+    pub answer: Result<Option<String>, String>,
+}
+
+/// A day's solution with its types erased, so that the runner can pick days at runtime
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+
+    /// Parses the input and converts the resulting model to JSON
+    fn dump(&self, input: &str) -> Result<Value>;
+
+    fn generate(&self, size: usize, seed: u64) -> String;
+
+    /// Parses the input and solves both parts, timing each phase. The parts are only run when
+    /// parsing succeeded.
+    fn run(&self, input: &str) -> Vec<PhaseRun>;
+}
+
+struct Registered<S>(PhantomData<fn() -> S>);
+
+impl<S> Puzzle for Registered<S>
+where
+    S: Solution + Generator,
+    S::Model: Serialize,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn dump(&self, input: &str) -> Result<Value> {
+        let model = S::parse(input).map_err(|e| eyre!(e.into()))?;
+        Ok(serde_json::to_value(model)?)
+    }
+
+    fn generate(&self, size: usize, seed: u64) -> String {
+        S::generate(size, seed)
+    }
+
+    fn run(&self, input: &str) -> Vec<PhaseRun> {
+        let (model, measured) = measure(|| S::parse(input));
+        let model = match model {
+            Ok(model) => model,
+            Err(e) => return vec![phase_run::<S>(Phase::Parse, measured, Err(e))],
+        };
+        let parse = phase_run::<S>(Phase::Parse, measured, Ok(None));
+        let (answer, measured) = measure(|| S::part_1(&model).map(Some));
+        let part_1 = phase_run::<S>(Phase::Part1, measured, answer);
+        let (answer, measured) = measure(|| S::part_2(&model).map(Some));
+        let part_2 = phase_run::<S>(Phase::Part2, measured, answer);
+        vec![parse, part_1, part_2]
+    }
+}
+
+// Runs a phase, returning how long it took and what it allocated
+fn measure<T>(phase: impl FnOnce() -> T) -> (T, (Duration, Option<Allocations>)) {
+    let ((output, duration), allocations) = alloc::measure(|| {
+        let start = Instant::now();
+        let output = phase();
+        (output, start.elapsed())
+    });
+    (output, (duration, allocations))
+}
+
+fn phase_run<S: Solution>(
+    phase: Phase,
+    (duration, allocations): (Duration, Option<Allocations>),
+    answer: Result<Option<S::Answer>, S::Error>,
+) -> PhaseRun {
+    PhaseRun {
+        phase,
+        duration,
+        allocations,
+        answer: answer
+            .map(|answer| answer.map(|answer| answer.to_string()))
+            .map_err(|e| e.into().to_string()),
+    }
+}
+
+/// Every solved day, in order
+pub static DAYS: &[&dyn Puzzle] = &[
+    &Registered::<Day1>(PhantomData),
+    &Registered::<Day2>(PhantomData),
+    &Registered::<Day3>(PhantomData),
+    &Registered::<Day4>(PhantomData),
+    &Registered::<Day5>(PhantomData),
+    &Registered::<Day6>(PhantomData),
+];
+
+pub fn find(day: u8) -> Result<&'static dyn Puzzle> {
+    DAYS.iter()
+        .copied()
+        .find(|puzzle| puzzle.day() == day)
+        .ok_or_else(|| eyre!("day {day} has not been solved yet"))
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use serde_json::json;
+
+    use crate::days::{find, Phase, DAYS};
+
+    #[test]
+    fn registry_test() {
+        let days: Vec<u8> = DAYS.iter().map(|puzzle| puzzle.day()).collect();
+        assert_eq!((1..=6).collect::<Vec<_>>(), days);
+        assert!(find(25).is_err());
+    }
+
+    #[test]
+    fn dump_test() -> Result<()> {
+        let json = find(2)?.dump(include_str!("../../day2/src/test.txt"))?;
+        assert_eq!(5, json.as_array().unwrap().len());
+        assert_eq!(json!({"red": 4, "green": 0, "blue": 3}), json[0]["sets"][0]);
+        let json = find(6)?.dump(include_str!("../../day6/src/test.txt"))?;
+        // Integers are serialized however day 6 was built to store them
+        let int = |value: u8| serde_json::to_value(day6::Int::from(value));
+        assert_eq!(
+            json!({"time": int(7)?, "best_distance": int(9)?}),
+            json["races"][0]
+        );
+        assert!(find(4)?.dump("Card 1: 41 48").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn run_test() {
+        let runs = find(6)
+            .unwrap()
+            .run(include_str!("../../day6/src/test.txt"));
+        let answers: Vec<_> = runs
+            .iter()
+            .map(|run| (run.phase, run.answer.clone()))
+            .collect();
+        assert_eq!(
+            vec![
+                (Phase::Parse, Ok(None)),
+                (Phase::Part1, Ok(Some("288".to_string()))),
+                (Phase::Part2, Ok(Some("71503".to_string()))),
+            ],
+            answers
+        );
+        // Nothing is solved when parsing fails
+        let runs = find(4).unwrap().run("Card 1: 41 48");
+        assert_eq!(1, runs.len());
+        assert!(runs[0].answer.is_err());
+    }
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        for puzzle in DAYS {
+            let input = puzzle.generate(10, 1);
+            assert_eq!(input, puzzle.generate(10, 1));
+            puzzle.dump(&input)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process, thread,
+};
+
+use clap::{ArgGroup, Parser, Subcommand};
+use color_eyre::eyre::{Result, WrapErr};
+
+mod alloc;
+mod days;
+mod output;
+mod remote;
+mod scaffold;
+mod submit;
+mod timing;
+mod watch;
+
+use output::Format;
+use submit::{Attempt, AttemptLog, Verdict};
+
+/// Runs and inspects the Advent of Code 2023 solutions
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Parses an input and prints the parsed model as JSON
+    Dump {
+        #[arg(short, long)]
+        day: u8,
+        /// Input file to parse, `-` for stdin. Defaults to the downloaded input if there is
+        /// one, otherwise the day's own input.
+        input: Option<PathBuf>,
+    },
+    /// Solves one or every day and prints a table of how long each phase took
+    #[command(group(ArgGroup::new("days").required(true).args(["day", "all"])))]
+    Run {
+        #[arg(short, long)]
+        day: Option<u8>,
+        #[arg(long)]
+        all: bool,
+        /// Solve the days on a thread pool rather than one after the other
+        #[arg(long)]
+        parallel: bool,
+        /// Solve each day this many times and report the fastest and median times
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+        #[arg(short, long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Solves a day again every time its input changes, printing how the answers changed
+    Watch {
+        #[arg(short, long)]
+        day: u8,
+        /// Input file to watch. Defaults to the downloaded input if there is one, otherwise the
+        /// day's own input.
+        input: Option<PathBuf>,
+    },
+    /// Prints a synthetic input for a day
+    Generate {
+        #[arg(short, long)]
+        day: u8,
+        /// How many lines, races or map ranges to generate, depending on the day. Days 2 and 4
+        /// number their lines with a byte, so stop at 255
+        #[arg(short, long, default_value_t = 1000)]
+        size: usize,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Downloads a day's input into the cache, unless it is already there
+    Fetch {
+        #[arg(short, long)]
+        day: u8,
+    },
+    /// Submits an answer, unless earlier attempts already show what the verdict will be
+    Submit {
+        #[arg(short, long)]
+        day: u8,
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: String,
+        /// When rate limited, wait it out and submit again rather than giving up
+        #[arg(long)]
+        wait: bool,
+    },
+    /// Creates the crate for a new day from a template and adds it to the workspace
+    New {
+        #[arg(short, long)]
+        day: u8,
+    },
+}
+
+// The workspace this runner was built in, which holds every day's crate
+fn workspace_root() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+}
+
+// The downloaded input for a day if there is one, otherwise the input in the day's crate
+fn default_input(day: u8) -> PathBuf {
+    Some(remote::cache_path(workspace_root(), day))
+        .filter(|path| path.exists())
+        .unwrap_or_else(|| workspace_root().join(format!("day{day}/src/input.txt")))
+}
+
+fn read_input(day: u8, path: Option<&Path>) -> Result<String> {
+    let path = match path {
+        Some(path) if path == Path::new("-") => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            return Ok(input);
+        }
+        Some(path) => path.to_path_buf(),
+        None => default_input(day),
+    };
+    fs::read_to_string(&path).wrap_err_with(|| format!("could not read {}", path.display()))
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    match Cli::parse().command {
+        Command::Dump { day, input } => {
+            let puzzle = days::find(day)?;
+            let input = read_input(day, input.as_deref())?;
+            let model = puzzle.dump(&input)?;
+            let mut stdout = io::stdout().lock();
+            serde_json::to_writer_pretty(&mut stdout, &model)?;
+            writeln!(stdout)?;
+        }
+        Command::Run {
+            day,
+            all: _,
+            parallel,
+            runs,
+            format,
+        } => {
+            let puzzles = match day {
+                Some(day) => vec![days::find(day)?],
+                None => days::DAYS.to_vec(),
+            };
+            let input = |day| read_input(day, None);
+            let timings = timing::time_days(&puzzles, input, runs as usize, parallel);
+            output::write(&mut io::stdout().lock(), format, &timings, runs as usize)?;
+            // The tables only have room for the first line of each error
+            let mut failed = false;
+            for timing in &timings {
+                if let Err(error) = &timing.answer {
+                    eprintln!("\nday {} {} failed:\n{error}", timing.day, timing.phase);
+                    failed = true;
+                }
+            }
+            if failed {
+                process::exit(1);
+            }
+        }
+        Command::Watch { day, input } => {
+            let puzzle = days::find(day)?;
+            let input = input.unwrap_or_else(|| default_input(day));
+            watch::watch(
+                puzzle,
+                &input,
+                &workspace_root().join(format!("day{day}/src")),
+            )?;
+        }
+        Command::Generate { day, size, seed } => {
+            let input = days::find(day)?.generate(size, seed);
+            io::stdout().lock().write_all(input.as_bytes())?;
+        }
+        Command::Fetch { day } => {
+            let path = remote::cache_path(workspace_root(), day);
+            remote::cached_input(&path, || {
+                remote::Client::new(remote::BASE_URL, &remote::session()?, &remote::contact()?)
+                    .input(day)
+            })?;
+            println!("{}", path.canonicalize()?.display());
+        }
+        Command::Submit {
+            day,
+            part,
+            answer,
+            wait,
+        } => {
+            let mut log = AttemptLog::open(&AttemptLog::path(workspace_root()))?;
+            if let Some(verdict) = log.known_verdict(day, part, &answer) {
+                println!("{verdict} (known from an earlier attempt, not submitted)");
+                return Ok(());
+            }
+            let client =
+                remote::Client::new(remote::BASE_URL, &remote::session()?, &remote::contact()?);
+            loop {
+                let verdict = client.submit(day, part, &answer)?;
+                log.record(Attempt {
+                    day,
+                    part,
+                    answer: answer.clone(),
+                    verdict,
+                })?;
+                match verdict {
+                    Verdict::RateLimited(delay) if wait => {
+                        eprintln!("{verdict}, waiting");
+                        thread::sleep(delay);
+                    }
+                    verdict => {
+                        println!("{verdict}");
+                        break;
+                    }
+                }
+            }
+        }
+        Command::New { day } => {
+            let dir = scaffold::new_day(workspace_root(), day)?;
+            println!("Created {}", dir.canonicalize()?.display());
+        }
+    }
+    Ok(())
+}
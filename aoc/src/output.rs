@@ -0,0 +1,334 @@
+use std::io::Write;
+
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+use serde::Serialize;
+
+use crate::{days::Phase, timing::Timing};
+
+/// How the results of solving days are written out
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// A table lined up in columns, for reading in a terminal
+    #[default]
+    Text,
+    /// An array with an object per phase
+    Json,
+    /// A header row followed by a row per phase
+    Csv,
+    /// A GitHub flavoured Markdown table
+    Markdown,
+}
+
+// A phase as it is written to the machine readable formats. Both times are always there, as are
+// the allocation columns, so the shape does not depend on how the runner was invoked.
+#[derive(Serialize)]
+struct Record<'a> {
+    day: u8,
+    phase: Phase,
+    min_nanos: u128,
+    median_nanos: u128,
+    allocations: Option<u64>,
+    peak_bytes: Option<usize>,
+    answer: Option<&'a str>,
+    error: Option<&'a str>,
+}
+
+impl<'a> From<&'a Timing> for Record<'a> {
+    fn from(timing: &'a Timing) -> Self {
+        let (answer, error) = match &timing.answer {
+            Ok(answer) => (answer.as_deref(), None),
+            Err(error) => (None, Some(error.as_str())),
+        };
+        Record {
+            day: timing.day,
+            phase: timing.phase,
+            min_nanos: timing.min.as_nanos(),
+            median_nanos: timing.median.as_nanos(),
+            allocations: timing.allocations.map(|allocations| allocations.count),
+            peak_bytes: timing.allocations.map(|allocations| allocations.peak_bytes),
+            answer,
+            error,
+        }
+    }
+}
+
+/// Writes the timings of one or more days in the given format. `runs` is how many times each day
+/// was solved.
+pub fn write(out: &mut impl Write, format: Format, timings: &[Timing], runs: usize) -> Result<()> {
+    match format {
+        Format::Text => write_table(out, timings, runs),
+        Format::Json => write_json(out, timings),
+        Format::Csv => write_csv(out, timings),
+        Format::Markdown => write_markdown(out, timings, runs),
+    }
+}
+
+// The header and rows of the tables meant to be read by people. The median is left out for
+// single runs, where it is the same as the minimum, and allocations when they were not counted.
+fn cells(timings: &[Timing], runs: usize) -> (Vec<&'static str>, Vec<Vec<String>>) {
+    let mut header = vec!["day", "phase", "time"];
+    if runs > 1 {
+        header[2] = "min";
+        header.push("median");
+    }
+    let counted = timings.iter().any(|timing| timing.allocations.is_some());
+    if counted {
+        header.extend(["allocs", "peak"]);
+    }
+    header.push("answer");
+    let rows = timings
+        .iter()
+        .map(|timing| {
+            let mut row = vec![
+                timing.day.to_string(),
+                timing.phase.to_string(),
+                format!("{:.2?}", timing.min),
+            ];
+            if runs > 1 {
+                row.push(format!("{:.2?}", timing.median));
+            }
+            if counted {
+                let allocations = timing.allocations.unwrap_or_default();
+                row.push(allocations.count.to_string());
+                row.push(bytes(allocations.peak_bytes));
+            }
+            row.push(match &timing.answer {
+                Ok(answer) => answer.clone().unwrap_or_default(),
+                // Only the headline of an error fits in the table. Diagnostics start with their
+                // own "error: ", which is not repeated.
+                Err(error) => {
+                    let headline = error.lines().next().unwrap_or_default();
+                    format!("error: {}", headline.trim_start_matches("error: "))
+                }
+            });
+            row
+        })
+        .collect();
+    (header, rows)
+}
+
+fn write_table(out: &mut impl Write, timings: &[Timing], runs: usize) -> Result<()> {
+    let (header, rows) = cells(timings, runs);
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([header[column].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    let header = header.iter().map(|name| name.to_string()).collect();
+    for row in [header].iter().chain(&rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect();
+        writeln!(out, "{}", cells.join("  ").trim_end())?;
+    }
+    Ok(())
+}
+
+fn write_markdown(out: &mut impl Write, timings: &[Timing], runs: usize) -> Result<()> {
+    let (header, rows) = cells(timings, runs);
+    writeln!(out, "| {} |", header.join(" | "))?;
+    writeln!(out, "|{}", "---|".repeat(header.len()))?;
+    for row in rows {
+        let row: Vec<String> = row.iter().map(|cell| cell.replace('|', "\\|")).collect();
+        writeln!(out, "| {} |", row.join(" | "))?;
+    }
+    Ok(())
+}
+
+fn write_json(out: &mut impl Write, timings: &[Timing]) -> Result<()> {
+    let records: Vec<Record> = timings.iter().map(Record::from).collect();
+    serde_json::to_writer_pretty(&mut *out, &records)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+fn write_csv(out: &mut impl Write, timings: &[Timing]) -> Result<()> {
+    writeln!(
+        out,
+        "day,phase,min_nanos,median_nanos,allocations,peak_bytes,answer,error"
+    )?;
+    for timing in timings {
+        let record = Record::from(timing);
+        let optional = |value: Option<String>| value.unwrap_or_default();
+        let fields = [
+            record.day.to_string(),
+            serde_json::to_value(record.phase)?
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            record.min_nanos.to_string(),
+            record.median_nanos.to_string(),
+            optional(record.allocations.map(|count| count.to_string())),
+            optional(record.peak_bytes.map(|bytes| bytes.to_string())),
+            csv_field(record.answer.unwrap_or_default()),
+            csv_field(record.error.unwrap_or_default()),
+        ];
+        writeln!(out, "{}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+// Quotes a field when it holds a separator, quote or line break, doubling any quotes inside
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// Formats a number of bytes in the largest binary unit it has at least one of
+fn bytes(bytes: usize) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{bytes}B"),
+        unit => format!("{size:.2}{}", units[unit]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use color_eyre::eyre::Result;
+    use serde_json::json;
+
+    use crate::{
+        alloc::Allocations,
+        days::Phase,
+        output::{write, Format},
+        timing::Timing,
+    };
+
+    fn timings() -> [Timing; 3] {
+        let timing = |phase, millis, answer| Timing {
+            day: 5,
+            phase,
+            min: Duration::from_millis(millis),
+            median: Duration::from_millis(millis * 2),
+            allocations: None,
+            answer,
+        };
+        [
+            timing(Phase::Parse, 1, Ok(None)),
+            timing(Phase::Part1, 20, Ok(Some("35".into()))),
+            timing(Phase::Part2, 300, Err("seed range overflows\nmore".into())),
+        ]
+    }
+
+    fn written(format: Format, timings: &[Timing], runs: usize) -> Result<String> {
+        let mut out = Vec::new();
+        write(&mut out, format, timings, runs)?;
+        Ok(String::from_utf8(out)?)
+    }
+
+    #[test]
+    fn text_test() -> Result<()> {
+        assert_eq!(
+            "\
+day  phase   min       median    answer
+5    parse   1.00ms    2.00ms
+5    part 1  20.00ms   40.00ms   35
+5    part 2  300.00ms  600.00ms  error: seed range overflows
+",
+            written(Format::Text, &timings(), 3)?
+        );
+
+        let allocations = |count, peak_bytes| Some(Allocations { count, peak_bytes });
+        let [parse, part_1, _] = timings();
+        let timings = [
+            Timing {
+                allocations: allocations(3, 512),
+                ..parse
+            },
+            Timing {
+                allocations: allocations(40, 5 * 1024 * 1024 + 100),
+                ..part_1
+            },
+        ];
+        assert_eq!(
+            "\
+day  phase   time     allocs  peak     answer
+5    parse   1.00ms   3       512B
+5    part 1  20.00ms  40      5.00MiB  35
+",
+            written(Format::Text, &timings, 1)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn markdown_test() -> Result<()> {
+        let mut timings = timings();
+        timings[1].answer = Ok(Some("a|b".into()));
+        assert_eq!(
+            "\
+| day | phase | time | answer |
+|---|---|---|---|
+| 5 | parse | 1.00ms |  |
+| 5 | part 1 | 20.00ms | a\\|b |
+| 5 | part 2 | 300.00ms | error: seed range overflows |
+",
+            written(Format::Markdown, &timings, 1)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn json_test() -> Result<()> {
+        let json: serde_json::Value = serde_json::from_str(&written(Format::Json, &timings(), 3)?)?;
+        assert_eq!(
+            json!([
+                {
+                    "day": 5, "phase": "parse", "min_nanos": 1000000, "median_nanos": 2000000,
+                    "allocations": null, "peak_bytes": null, "answer": null, "error": null,
+                },
+                {
+                    "day": 5, "phase": "part1", "min_nanos": 20000000, "median_nanos": 40000000,
+                    "allocations": null, "peak_bytes": null, "answer": "35", "error": null,
+                },
+                {
+                    "day": 5, "phase": "part2", "min_nanos": 300000000,
+                    "median_nanos": 600000000, "allocations": null, "peak_bytes": null,
+                    "answer": null, "error": "seed range overflows\nmore",
+                },
+            ]),
+            json
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn csv_test() -> Result<()> {
+        let mut timings = timings();
+        timings[0].allocations = Some(Allocations {
+            count: 3,
+            peak_bytes: 512,
+        });
+        timings[1].answer = Ok(Some("say \"hi\", twice".into()));
+        assert_eq!(
+            "\
+day,phase,min_nanos,median_nanos,allocations,peak_bytes,answer,error
+5,parse,1000000,2000000,3,512,,
+5,part1,20000000,40000000,,,\"say \"\"hi\"\", twice\",
+5,part2,300000000,600000000,,,,\"seed range overflows
+more\"
+",
+            written(Format::Csv, &timings, 1)?
+        );
+        Ok(())
+    }
+}
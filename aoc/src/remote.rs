@@ -0,0 +1,221 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use ureq::Agent;
+
+use crate::submit::Verdict;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+const YEAR: u16 = 2023;
+
+// The site asks automated tools to say what they are and who to reach about them
+fn user_agent(contact: &str) -> String {
+    format!(
+        "aoc-2023-runner/{} (personal Advent of Code solutions runner, fetches each input once; \
+         contact: {contact})",
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// Talks to the Advent of Code website as the user the session token belongs to
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    /// Creates a client announcing `contact` as who to reach about its requests
+    pub fn new(base_url: &str, session: &str, contact: &str) -> Self {
+        Client {
+            agent: ureq::builder().user_agent(&user_agent(contact)).build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    /// Downloads the input for a day
+    pub fn input(&self, day: u8) -> Result<String> {
+        let url = format!("{}/{YEAR}/day/{day}/input", self.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|error| match error {
+                // The site explains what went wrong, such as the day not being unlocked yet
+                ureq::Error::Status(status, response) => eyre!(
+                    "could not download the input for day {day}, status {status}: {}",
+                    response.into_string().unwrap_or_default().trim()
+                ),
+                error => eyre!(error),
+            })?;
+        Ok(response.into_string()?)
+    }
+
+    /// Submits an answer for one part of a day
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict> {
+        let url = format!("{}/{YEAR}/day/{day}/answer", self.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|error| eyre!("could not submit the answer for day {day}: {error}"))?;
+        Verdict::parse(&response.into_string()?)
+    }
+}
+
+/// Reads the session token from `AOC_SESSION`, or failing that from `aoc/session` in the user's
+/// config directory
+pub fn session() -> Result<String> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")));
+    find_session(env::var("AOC_SESSION").ok(), config_dir.as_deref())
+}
+
+fn find_session(var: Option<String>, config_dir: Option<&Path>) -> Result<String> {
+    if let Some(session) = var.filter(|session| !session.trim().is_empty()) {
+        return Ok(session.trim().to_string());
+    }
+    let path = config_dir
+        .ok_or_else(|| eyre!("no session token, set AOC_SESSION"))?
+        .join("aoc/session");
+    let session = fs::read_to_string(&path).wrap_err_with(|| {
+        format!(
+            "no session token, set AOC_SESSION or write it to {}",
+            path.display()
+        )
+    })?;
+    Ok(session.trim().to_string())
+}
+
+/// Reads who to reach about the runner's requests from `AOC_CONTACT`, such as an email address,
+/// or failing that uses the repository the runner is published from
+pub fn contact() -> Result<String> {
+    find_contact(env::var("AOC_CONTACT").ok(), env!("CARGO_PKG_REPOSITORY"))
+}
+
+fn find_contact(var: Option<String>, repository: &str) -> Result<String> {
+    [var.as_deref().unwrap_or_default(), repository]
+        .into_iter()
+        .map(str::trim)
+        .find(|contact| !contact.is_empty())
+        .map(String::from)
+        .ok_or_else(|| {
+            eyre!("no contact to send along with requests, set AOC_CONTACT to an email or a URL")
+        })
+}
+
+/// Where a day's downloaded input is kept. The cache is git-ignored, as inputs are not meant to
+/// be shared.
+pub fn cache_path(root: &Path, day: u8) -> PathBuf {
+    root.join(format!(".cache/inputs/day{day}.txt"))
+}
+
+/// Returns the cached input at `path`, only calling `download` and filling the cache when there
+/// is nothing cached yet
+pub fn cached_input(path: &Path, download: impl FnOnce() -> Result<String>) -> Result<String> {
+    if path.exists() {
+        return fs::read_to_string(path)
+            .wrap_err_with(|| format!("could not read {}", path.display()));
+    }
+    let input = download()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, &input).wrap_err_with(|| format!("could not write {}", path.display()))?;
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use color_eyre::eyre::Result;
+    use mockito::{Matcher, Server};
+
+    use crate::remote::{cache_path, cached_input, find_contact, find_session, Client};
+
+    #[test]
+    fn input_test() -> Result<()> {
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/2023/day/6/input")
+            .match_header("cookie", "session=abc123")
+            .match_header(
+                "user-agent",
+                Matcher::Regex("^aoc-2023-runner/.*contact: me@example.com".into()),
+            )
+            .with_body("Time: 7\nDistance: 9\n")
+            .expect(1)
+            .create();
+        let client = Client::new(&server.url(), "abc123", "me@example.com");
+        let cache = tempfile::tempdir()?;
+        let path = cache_path(cache.path(), 6);
+        for _ in 0..2 {
+            let input = cached_input(&path, || client.input(6))?;
+            assert_eq!("Time: 7\nDistance: 9\n", input);
+        }
+        // The second call is answered from the cache
+        mock.assert();
+        assert_eq!("Time: 7\nDistance: 9\n", fs::read_to_string(path)?);
+        Ok(())
+    }
+
+    #[test]
+    fn input_error_test() -> Result<()> {
+        let mut server = Server::new();
+        server
+            .mock("GET", "/2023/day/25/input")
+            .with_status(404)
+            .with_body("Please don't repeatedly request this endpoint before it unlocks!\n")
+            .create();
+        let cache = tempfile::tempdir()?;
+        let path = cache_path(cache.path(), 25);
+        let client = Client::new(&server.url(), "abc123", "me@example.com");
+        let error = cached_input(&path, || client.input(25)).unwrap_err();
+        assert!(error.to_string().contains("status 404: Please don't"));
+        // Errors are never cached
+        assert!(!path.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn session_test() -> Result<()> {
+        let config = tempfile::tempdir()?;
+        assert_eq!("abc", find_session(Some("abc\n".into()), None)?);
+        assert!(find_session(None, Some(config.path())).is_err());
+        fs::create_dir(config.path().join("aoc"))?;
+        fs::write(config.path().join("aoc/session"), "def\n")?;
+        assert_eq!("def", find_session(None, Some(config.path()))?);
+        assert_eq!(
+            "abc",
+            find_session(Some("abc".into()), Some(config.path()))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn contact_test() -> Result<()> {
+        assert_eq!(
+            "me@example.com",
+            find_contact(Some(" me@example.com\n".into()), "")?
+        );
+        assert_eq!(
+            "https://example.com/aoc",
+            find_contact(Some(String::new()), "https://example.com/aoc")?
+        );
+        assert!(find_contact(None, "").is_err());
+        // The runner falls back to the repository it is built from
+        assert_eq!(
+            "https://github.com/vanhouc/aoc2023",
+            find_contact(None, env!("CARGO_PKG_REPOSITORY"))?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use toml_edit::Document;
+
+// Files making up a new day's crate, relative to its directory, and the template for each
+const TEMPLATES: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.tmpl")),
+    ("build.rs", include_str!("../templates/build.rs.tmpl")),
+    ("src/lib.rs", include_str!("../templates/lib.rs.tmpl")),
+    ("src/error.rs", include_str!("../templates/error.rs.tmpl")),
+    ("src/main.rs", include_str!("../templates/main.rs.tmpl")),
+    ("src/input.txt", ""),
+    ("src/test.txt", ""),
+    ("examples/puzzle.txt", "part 1: 0\npart 2: 0\n---\n"),
+    (
+        "benches/day{{day}}.rs",
+        include_str!("../templates/bench.rs.tmpl"),
+    ),
+];
+
+/// Creates the crate for a new day in the workspace at `root` and adds it to the workspace
+/// members. Returns the directory of the new crate.
+pub fn new_day(root: &Path, day: u8) -> Result<PathBuf> {
+    if !(1..=25).contains(&day) {
+        bail!("day {day} is not a day of Advent of Code");
+    }
+    let name = format!("day{day}");
+    let dir = root.join(&name);
+    if dir.exists() {
+        bail!("{} already exists", dir.display());
+    }
+    // Update the manifest in memory first, so nothing is written if it can not be
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path)
+        .wrap_err_with(|| format!("could not read {}", manifest_path.display()))?;
+    let manifest = add_member(&manifest, &name)?;
+
+    for (path, template) in TEMPLATES {
+        let path = dir.join(path.replace("{{day}}", &day.to_string()));
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, template.replace("{{day}}", &day.to_string()))
+            .wrap_err_with(|| format!("could not write {}", path.display()))?;
+    }
+    fs::write(&manifest_path, manifest)?;
+    Ok(dir)
+}
+
+// Inserts a member into the workspace manifest, keeping the days in numeric order
+fn add_member(manifest: &str, name: &str) -> Result<String> {
+    let mut document: Document = manifest.parse()?;
+    let members = document["workspace"]["members"]
+        .as_array_mut()
+        .ok_or_else(|| eyre!("workspace manifest has no members list"))?;
+    if members.iter().any(|member| member.as_str() == Some(name)) {
+        bail!("{name} is already a workspace member");
+    }
+    let day = |member: &str| member.strip_prefix("day")?.parse::<u8>().ok();
+    let new_day = day(name);
+    let index = members
+        .iter()
+        .position(|member| member.as_str().and_then(day) > new_day)
+        .unwrap_or(members.len());
+    members.insert(index, name);
+    members.fmt();
+    Ok(document.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use color_eyre::eyre::Result;
+
+    use crate::scaffold::{add_member, new_day};
+
+    #[test]
+    fn add_member_test() -> Result<()> {
+        let manifest = "[workspace]\nmembers = [\"aoc\", \"day1\", \"day2\", \"day10\"]\n";
+        assert_eq!(
+            "[workspace]\nmembers = [\"aoc\", \"day1\", \"day2\", \"day3\", \"day10\"]\n",
+            add_member(manifest, "day3")?
+        );
+        assert_eq!(
+            "[workspace]\nmembers = [\"aoc\", \"day1\", \"day2\", \"day10\", \"day11\"]\n",
+            add_member(manifest, "day11")?
+        );
+        assert!(add_member(manifest, "day2").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn new_day_test() -> Result<()> {
+        let root = tempfile::tempdir()?;
+        let manifest = root.path().join("Cargo.toml");
+        fs::write(&manifest, "[workspace]\nmembers = [\"common\", \"day1\"]\n")?;
+        let dir = new_day(root.path(), 7)?;
+        assert_eq!(root.path().join("day7"), dir);
+        let lib = fs::read_to_string(dir.join("src/lib.rs"))?;
+        assert!(lib.contains("impl Solution for Day7 {"));
+        assert!(lib.contains("const DAY: u8 = 7;"));
+        assert!(dir.join("src/error.rs").exists());
+        assert!(dir.join("src/test.txt").exists());
+        assert!(dir.join("build.rs").exists());
+        assert!(dir.join("examples/puzzle.txt").exists());
+        assert!(dir.join("benches/day7.rs").exists());
+        assert_eq!(
+            "[workspace]\nmembers = [\"common\", \"day1\", \"day7\"]\n",
+            fs::read_to_string(&manifest)?
+        );
+        // An existing day is never overwritten
+        fs::write(dir.join("src/lib.rs"), "solved")?;
+        assert!(new_day(root.path(), 7).is_err());
+        assert_eq!("solved", fs::read_to_string(dir.join("src/lib.rs"))?);
+        assert!(new_day(root.path(), 26).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use color_eyre::eyre::{bail, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// What the website made of a submitted answer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Verdict {
+    Correct,
+    /// Wrong, with a hint on which way the right answer lies when the site gives one
+    Incorrect(Option<Hint>),
+    /// Answered too soon after the last attempt, and how long is left to wait
+    RateLimited(Duration),
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page the site answers a submission with
+    pub fn parse(page: &str) -> Result<Verdict> {
+        let verdict = if page.contains("That's the right answer") {
+            Verdict::Correct
+        } else if page.contains("That's not the right answer") {
+            let hint = if page.contains("your answer is too high") {
+                Some(Hint::TooHigh)
+            } else if page.contains("your answer is too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            Verdict::Incorrect(hint)
+        } else if page.contains("You gave an answer too recently") {
+            Verdict::RateLimited(parse_wait(page).unwrap_or(Duration::from_secs(60)))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            bail!("could not understand the response to the answer");
+        };
+        Ok(verdict)
+    }
+}
+
+// Reads waits such as "You have 35s left to wait" or "You have 4m 10s left to wait"
+fn parse_wait(page: &str) -> Option<Duration> {
+    let start = page.find("You have ")? + "You have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+    page[start..end]
+        .split_whitespace()
+        .try_fold(0, |seconds, amount| {
+            let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            let scale = match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+            Some(seconds + number * scale)
+        })
+        .map(Duration::from_secs)
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect(None) => write!(f, "incorrect"),
+            Verdict::Incorrect(Some(Hint::TooHigh)) => write!(f, "incorrect, too high"),
+            Verdict::Incorrect(Some(Hint::TooLow)) => write!(f, "incorrect, too low"),
+            Verdict::RateLimited(wait) => {
+                write!(f, "rate limited, try again in {}s", wait.as_secs())
+            }
+            Verdict::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Every answer submitted so far, kept as JSON lines so that wrong answers are never sent twice
+pub struct AttemptLog {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl AttemptLog {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(".cache/attempts.jsonl")
+    }
+
+    pub fn open(path: &Path) -> Result<Self> {
+        let attempts = match fs::read_to_string(path) {
+            Ok(log) => log
+                .lines()
+                .map(serde_json::from_str)
+                .collect::<Result<_, _>>()
+                .wrap_err_with(|| format!("{} is not a valid attempt log", path.display()))?,
+            Err(_) if !path.exists() => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(AttemptLog {
+            path: path.to_path_buf(),
+            attempts,
+        })
+    }
+
+    /// The verdict an answer is already known to get from earlier attempts, if any. Besides the
+    /// same answer being tried before, a solved part rejects everything and numeric answers can
+    /// be ruled out by earlier "too high" and "too low" hints.
+    pub fn known_verdict(&self, day: u8, part: u8, answer: &str) -> Option<Verdict> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.day == day && attempt.part == part);
+        let number = answer.parse::<i128>().ok();
+        let mut known = None;
+        for attempt in attempts {
+            let verdict = match attempt.verdict {
+                Verdict::RateLimited(_) => continue,
+                verdict if attempt.answer == answer => verdict,
+                Verdict::Correct | Verdict::AlreadySolved => Verdict::AlreadySolved,
+                Verdict::Incorrect(Some(hint)) => {
+                    let bound = attempt.answer.parse::<i128>().ok();
+                    match (number, bound, hint) {
+                        (Some(n), Some(bound), Hint::TooHigh) if n >= bound => {
+                            Verdict::Incorrect(Some(hint))
+                        }
+                        (Some(n), Some(bound), Hint::TooLow) if n <= bound => {
+                            Verdict::Incorrect(Some(hint))
+                        }
+                        _ => continue,
+                    }
+                }
+                Verdict::Incorrect(None) => continue,
+            };
+            known = Some(verdict);
+        }
+        known
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&attempt)?)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use color_eyre::eyre::Result;
+    use mockito::{Matcher, Server};
+
+    use crate::{
+        remote::Client,
+        submit::{Attempt, AttemptLog, Hint, Verdict},
+    };
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn verdict_test() -> Result<()> {
+        let cases = [
+            (
+                "That's the right answer!  You are one gold star closer to restoring snow operations.",
+                Verdict::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.  If you're stuck, ...",
+                Verdict::Incorrect(Some(Hint::TooHigh)),
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Verdict::Incorrect(Some(Hint::TooLow)),
+            ),
+            (
+                "That's not the right answer.  If you're stuck, ...",
+                Verdict::Incorrect(None),
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 10s left to wait.",
+                Verdict::RateLimited(Duration::from_secs(250)),
+            ),
+            (
+                "You gave an answer too recently. You have 35s left to wait.",
+                Verdict::RateLimited(Duration::from_secs(35)),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Verdict::AlreadySolved,
+            ),
+        ];
+        for (message, verdict) in cases {
+            assert_eq!(verdict, Verdict::parse(&page(message))?);
+        }
+        assert!(Verdict::parse("<html></html>").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn submit_test() -> Result<()> {
+        let mut server = Server::new();
+        let mock = server
+            .mock("POST", "/2023/day/6/answer")
+            .match_header("cookie", "session=abc123")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("level".into(), "2".into()),
+                Matcher::UrlEncoded("answer".into(), "71503".into()),
+            ]))
+            .with_body(page("That's the right answer!"))
+            .expect(1)
+            .create();
+        let client = Client::new(&server.url(), "abc123", "me@example.com");
+        assert_eq!(Verdict::Correct, client.submit(6, 2, "71503")?);
+        mock.assert();
+        Ok(())
+    }
+
+    #[test]
+    fn attempt_log_test() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = AttemptLog::path(dir.path());
+        let mut log = AttemptLog::open(&path)?;
+        let attempt = |answer: &str, verdict| Attempt {
+            day: 1,
+            part: 1,
+            answer: answer.into(),
+            verdict,
+        };
+        log.record(attempt("100", Verdict::Incorrect(Some(Hint::TooHigh))))?;
+        log.record(attempt("10", Verdict::Incorrect(Some(Hint::TooLow))))?;
+        log.record(attempt("abc", Verdict::Incorrect(None)))?;
+        log.record(attempt("50", Verdict::RateLimited(Duration::from_secs(5))))?;
+        // The log is read back the next time the runner starts
+        let log = AttemptLog::open(&path)?;
+        let known = |answer| log.known_verdict(1, 1, answer);
+        assert_eq!(Some(Verdict::Incorrect(Some(Hint::TooHigh))), known("150"));
+        assert_eq!(Some(Verdict::Incorrect(Some(Hint::TooLow))), known("10"));
+        assert_eq!(Some(Verdict::Incorrect(None)), known("abc"));
+        assert_eq!(None, known("50"));
+        assert_eq!(None, log.known_verdict(1, 2, "150"));
+
+        let mut log = log;
+        log.record(attempt("42", Verdict::Correct))?;
+        assert_eq!(Some(Verdict::Correct), log.known_verdict(1, 1, "42"));
+        assert_eq!(Some(Verdict::AlreadySolved), log.known_verdict(1, 1, "43"));
+        Ok(())
+    }
+}
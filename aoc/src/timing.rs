@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use color_eyre::eyre::Result;
+use rayon::prelude::*;
+
+use crate::{
+    alloc::Allocations,
+    days::{Phase, Puzzle},
+};
+
+/// A phase of a day timed over one or more runs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timing {
+    pub day: u8,
+    pub phase: Phase,
+    pub min: Duration,
+    pub median: Duration,
+    /// Allocations are the same every run, so they are taken from the first
+    pub allocations: Option<Allocations>,
+    /// As for [`crate::days::PhaseRun::answer`], taken from the first run
+    pub answer: Result<Option<String>, String>,
+}
+
+/// Solves a day `runs` times over and reports the fastest and median time of each phase
+pub fn time(puzzle: &dyn Puzzle, input: &str, runs: usize) -> Vec<Timing> {
+    let first = puzzle.run(input);
+    let mut durations: Vec<Vec<Duration>> = first.iter().map(|run| vec![run.duration]).collect();
+    for _ in 1..runs {
+        // Solutions are deterministic, so every run goes through the same phases
+        for (durations, run) in durations.iter_mut().zip(puzzle.run(input)) {
+            durations.push(run.duration);
+        }
+    }
+    first
+        .into_iter()
+        .zip(durations)
+        .map(|(run, mut durations)| {
+            durations.sort();
+            Timing {
+                day: puzzle.day(),
+                phase: run.phase,
+                min: durations[0],
+                median: durations[durations.len() / 2],
+                allocations: run.allocations,
+                answer: run.answer,
+            }
+        })
+        .collect()
+}
+
+/// Times every given day, on a thread pool when `parallel` is set. Timings come back in the
+/// order the days were given whichever finishes first. A day whose input can not be read gets a
+/// failed parse phase.
+pub fn time_days(
+    puzzles: &[&dyn Puzzle],
+    input: impl Fn(u8) -> Result<String> + Sync,
+    runs: usize,
+    parallel: bool,
+) -> Vec<Timing> {
+    let time_day = |puzzle: &&dyn Puzzle| match input(puzzle.day()) {
+        Ok(input) => time(*puzzle, &input, runs),
+        Err(e) => vec![Timing {
+            day: puzzle.day(),
+            phase: Phase::Parse,
+            min: Duration::ZERO,
+            median: Duration::ZERO,
+            allocations: None,
+            answer: Err(format!("{e:#}")),
+        }],
+    };
+    let days: Vec<Vec<Timing>> = if parallel {
+        puzzles.par_iter().map(time_day).collect()
+    } else {
+        puzzles.iter().map(time_day).collect()
+    };
+    days.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::{eyre, Result};
+
+    use crate::{
+        days::{find, Phase},
+        timing::{time, time_days},
+    };
+
+    #[test]
+    fn time_test() -> Result<()> {
+        let timings = time(find(2)?, include_str!("../../day2/src/test.txt"), 5);
+        let phases: Vec<Phase> = timings.iter().map(|timing| timing.phase).collect();
+        assert_eq!(vec![Phase::Parse, Phase::Part1, Phase::Part2], phases);
+        assert!(timings.iter().all(|timing| timing.min <= timing.median));
+        assert_eq!(Ok(Some("2286".to_string())), timings[2].answer);
+        Ok(())
+    }
+
+    #[test]
+    fn time_days_test() -> Result<()> {
+        let puzzles = [find(6)?, find(2)?, find(4)?];
+        let input = |day| match day {
+            2 => Ok(include_str!("../../day2/src/test.txt").to_string()),
+            4 => Err(eyre!("could not read day4/src/input.txt")),
+            _ => Ok(include_str!("../../day6/src/test.txt").to_string()),
+        };
+        for parallel in [false, true] {
+            let timings = time_days(&puzzles, input, 1, parallel);
+            let days: Vec<(u8, Phase)> = timings
+                .iter()
+                .map(|timing| (timing.day, timing.phase))
+                .collect();
+            assert_eq!(
+                vec![
+                    (6, Phase::Parse),
+                    (6, Phase::Part1),
+                    (6, Phase::Part2),
+                    (2, Phase::Parse),
+                    (2, Phase::Part1),
+                    (2, Phase::Part2),
+                    (4, Phase::Parse),
+                ],
+                days
+            );
+            assert_eq!(Ok(Some("71503".to_string())), timings[2].answer);
+            assert_eq!(
+                Err("could not read day4/src/input.txt".to_string()),
+                timings[6].answer
+            );
+        }
+        Ok(())
+    }
+}
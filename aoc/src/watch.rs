@@ -0,0 +1,174 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use color_eyre::eyre::Result;
+
+use crate::days::{Phase, PhaseRun, Puzzle};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+// What is known about a file when it was last looked at, or nothing if it did not exist
+type Snapshot = Option<(SystemTime, u64)>;
+
+fn snapshot(path: &Path) -> Snapshot {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Notices changes to a set of files by polling their modification times and sizes
+pub struct Poller {
+    files: Vec<(PathBuf, Snapshot)>,
+}
+
+impl Poller {
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let snapshot = snapshot(&path);
+                (path, snapshot)
+            })
+            .collect();
+        Poller { files }
+    }
+
+    /// The files that were changed, created or removed since the last call
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, last) in &mut self.files {
+            let current = snapshot(path);
+            if current != *last {
+                *last = current;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+fn describe(answer: &Result<Option<String>, String>) -> String {
+    match answer {
+        Ok(answer) => answer.clone().unwrap_or_default(),
+        Err(error) => {
+            let headline = error.lines().next().unwrap_or_default();
+            format!("error: {}", headline.trim_start_matches("error: "))
+        }
+    }
+}
+
+/// Describes the answers of a run, next to those of the previous run where they changed. Parsing
+/// only gets a line when it failed.
+pub fn diff(previous: &[PhaseRun], current: &[PhaseRun]) -> Vec<String> {
+    current
+        .iter()
+        .filter(|run| run.phase != Phase::Parse || run.answer.is_err())
+        .map(|run| {
+            let now = describe(&run.answer);
+            let before = previous.iter().find(|before| before.phase == run.phase);
+            // A parse that succeeded before has nothing to compare against
+            let before = before.map(|before| describe(&before.answer));
+            match before.filter(|before| !before.is_empty()) {
+                Some(before) if before == now => format!("{:<6}  {now} (unchanged)", run.phase),
+                Some(before) => format!("{:<6}  {before} -> {now}", run.phase),
+                None => format!("{:<6}  {now}", run.phase),
+            }
+        })
+        .collect()
+}
+
+/// Solves a day whenever its input changes, printing how the answers changed. The solution is
+/// built into the runner, so changes to its source are only pointed out.
+pub fn watch(puzzle: &dyn Puzzle, input: &Path, source: &Path) -> Result<()> {
+    let day = puzzle.day();
+    let sources: Vec<PathBuf> = fs::read_dir(source)?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "rs"))
+        .collect();
+    let mut inputs = Poller::new([input.to_path_buf()]);
+    let mut sources = Poller::new(sources);
+    let mut previous = Vec::new();
+    let mut solve = |event: &str| {
+        println!("[day {day}] {event}");
+        let runs = match fs::read_to_string(input) {
+            Ok(input) => puzzle.run(&input),
+            Err(e) => {
+                println!("  could not read {}: {e}", input.display());
+                return;
+            }
+        };
+        for line in diff(&previous, &runs) {
+            println!("  {line}");
+        }
+        previous = runs;
+    };
+    solve(&format!("solved {}", input.display()));
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        if !inputs.changed().is_empty() {
+            solve(&format!("{} changed", input.display()));
+        }
+        for path in sources.changed() {
+            println!(
+                "[day {day}] {} changed, rebuild the runner to solve with it",
+                path.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use color_eyre::eyre::Result;
+
+    use crate::{
+        days::find,
+        watch::{diff, Poller},
+    };
+
+    #[test]
+    fn poller_test() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let input = dir.path().join("input.txt");
+        let other = dir.path().join("other.txt");
+        fs::write(&input, "1")?;
+        let mut poller = Poller::new([input.clone(), other.clone()]);
+        assert!(poller.changed().is_empty());
+        // Sizes differ so that the change shows even when the modification time does not
+        fs::write(&input, "12")?;
+        assert_eq!(vec![input.clone()], poller.changed());
+        assert!(poller.changed().is_empty());
+        fs::write(&other, "1")?;
+        fs::remove_file(&input)?;
+        assert_eq!(vec![input, other], poller.changed());
+        Ok(())
+    }
+
+    #[test]
+    fn diff_test() -> Result<()> {
+        let puzzle = find(6)?;
+        let first = puzzle.run("Time: 7 15\nDistance: 9 40\n");
+        assert_eq!(vec!["part 1  32", "part 2  712"], diff(&[], &first));
+        let second = puzzle.run("Time: 7 16\nDistance: 9 40\n");
+        assert_eq!(
+            vec!["part 1  32 -> 36", "part 2  712 -> 713"],
+            diff(&first, &second)
+        );
+        assert_eq!(
+            vec!["part 1  36 (unchanged)", "part 2  713 (unchanged)"],
+            diff(&second, &second)
+        );
+        let broken = puzzle.run("Time: 7\n");
+        assert_eq!(1, diff(&second, &broken).len());
+        assert_eq!(
+            vec!["parse   error: leaderboard should have exactly two lines"],
+            diff(&second, &broken)
+        );
+        Ok(())
+    }
+}
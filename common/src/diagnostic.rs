@@ -0,0 +1,155 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    num::ParseIntError,
+    ops::Range,
+};
+
+/// A parse error pointing at the offending token of an input line.
+///
+/// Displaying it renders the line as an annotated snippet:
+///
+/// ```text
+/// error: invalid cube count
+///  --> line 3, column 9
+///   |
+/// 3 | Game 3: 8x green, 6 blue
+///   |         ^^ invalid digit found in string
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub message: String,
+    /// 1-based number of the line within the input
+    pub line_number: usize,
+    pub line: String,
+    /// Byte range of the offending token within `line`
+    pub span: Range<usize>,
+    pub label: String,
+    /// Why a number could not be parsed, when that is what went wrong
+    pub source: Option<ParseIntError>,
+}
+
+// Byte offset of token within source, if it is a slice of it
+fn offset_in(source: &str, token: &str) -> Option<usize> {
+    (token.as_ptr() as usize)
+        .checked_sub(source.as_ptr() as usize)
+        .filter(|offset| offset + token.len() <= source.len())
+}
+
+/// 1-based number of the line of `source` that `token`, a slice of it, starts on
+pub fn line_number(source: &str, token: &str) -> usize {
+    let offset = offset_in(source, token).unwrap_or_default();
+    source[..offset].matches('\n').count() + 1
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic for `token`, which must be a slice of `source`.
+    ///
+    /// The source may be a single line or a whole puzzle input, the line containing the token
+    /// is located either way. A token that does not point into the source is reported against
+    /// the whole first line instead.
+    pub fn new(
+        source: &str,
+        token: &str,
+        message: impl Into<String>,
+        label: impl Into<String>,
+    ) -> Self {
+        let (start, end) = match offset_in(source, token) {
+            Some(offset) => (offset, offset + token.len()),
+            None => (0, source.find('\n').unwrap_or(source.len())),
+        };
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[start..]
+            .find('\n')
+            .map_or(source.len(), |i| start + i);
+        let line = source[line_start..line_end].trim_end_matches('\r');
+        let line_number = source[..line_start].matches('\n').count() + 1;
+        // Tokens running over the end of the line are cut off at it
+        let span_start = (start - line_start).min(line.len());
+        let span_end = (end - line_start).clamp(span_start, line.len());
+        Diagnostic {
+            message: message.into(),
+            line_number,
+            line: line.to_string(),
+            span: span_start..span_end,
+            label: label.into(),
+            source: None,
+        }
+    }
+
+    /// Attaches the error a number failed to parse with
+    pub fn with_source(mut self, source: ParseIntError) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Moves a diagnostic produced for a single line to that line's number in the whole input
+    pub fn with_line_number(mut self, line_number: usize) -> Self {
+        self.line_number = line_number;
+        self
+    }
+
+    /// 1-based character column where the span starts
+    pub fn column(&self) -> usize {
+        self.line[..self.span.start].chars().count() + 1
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let number = self.line_number.to_string();
+        let gutter = " ".repeat(number.len());
+        let padding = " ".repeat(self.column() - 1);
+        let carets = "^".repeat(self.line[self.span.clone()].chars().count().max(1));
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{gutter}--> line {number}, column {}", self.column())?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{number} | {}", self.line)?;
+        write!(f, "{gutter} | {padding}{carets} {}", self.label)
+    }
+}
+
+impl Error for Diagnostic {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_ref().map(|source| source as _)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Diagnostic;
+
+    #[test]
+    fn render_test() {
+        let input = "Game 1: 3 blue\nGame 2: 8x green, 6 blue\n";
+        let token = &input[23..25];
+        let diagnostic = Diagnostic::new(
+            input,
+            token,
+            "invalid cube count",
+            "invalid digit found in string",
+        );
+        assert_eq!(2, diagnostic.line_number);
+        assert_eq!(8..10, diagnostic.span);
+        assert_eq!(
+            "error: invalid cube count\n --> line 2, column 9\n  |\n2 | Game 2: 8x green, 6 blue\n  |         ^^ invalid digit found in string",
+            diagnostic.to_string()
+        );
+    }
+
+    #[test]
+    fn end_of_line_test() {
+        let input = "Card 1 41 48\r\nCard 2";
+        let diagnostic = Diagnostic::new(input, &input[12..12], "missing colon", "expected ':'");
+        assert_eq!(
+            (1, 12..12),
+            (diagnostic.line_number, diagnostic.span.clone())
+        );
+        assert!(diagnostic
+            .to_string()
+            .ends_with("  |             ^ expected ':'"));
+        let diagnostic = Diagnostic::new(input, "elsewhere", "missing colon", "expected ':'")
+            .with_line_number(7);
+        assert_eq!((7, 0..12), (diagnostic.line_number, diagnostic.span));
+    }
+}
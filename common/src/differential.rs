@@ -0,0 +1,117 @@
+//! Differential testing of two solvers for the same day, typically a straightforward reference
+//! and an optimized candidate, on generated inputs
+
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    ops::Range,
+};
+
+use crate::{generate::Generator, Solution};
+
+/// The first generated input two solvers gave different answers for
+#[derive(Debug, PartialEq, Eq)]
+pub struct Disagreement<A> {
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub reference: A,
+    pub candidate: A,
+}
+
+impl<A: Debug> Display for Disagreement<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Disagreement {
+            seed,
+            size,
+            input,
+            reference,
+            candidate,
+        } = self;
+        writeln!(
+            f,
+            "solvers disagree on the input of size {size} generated from seed {seed}"
+        )?;
+        writeln!(f, "reference: {reference:?}")?;
+        writeln!(f, "candidate: {candidate:?}")?;
+        write!(f, "input:\n{input}")
+    }
+}
+
+impl<A: Debug> Error for Disagreement<A> {}
+
+/// Solves an input generated by `G` for every seed in `seeds` with both solvers, stopping at the
+/// first one their answers differ on. Sizes go from 1 up to `max_size` and start over, so the
+/// smaller inputs are tried first.
+pub fn compare<G: Generator, A: PartialEq>(
+    seeds: Range<u64>,
+    max_size: usize,
+    reference: impl Fn(&str) -> A,
+    candidate: impl Fn(&str) -> A,
+) -> Result<(), Disagreement<A>> {
+    for seed in seeds {
+        let size = (seed % max_size.max(1) as u64) as usize + 1;
+        let input = G::generate(size, seed);
+        let (reference, candidate) = (reference(&input), candidate(&input));
+        if reference != candidate {
+            return Err(Disagreement {
+                seed,
+                size,
+                input,
+                reference,
+                candidate,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Both answers `S` gives for an input, written the way they are printed, or why it could not
+/// solve the input. References can produce the same to be compared against a solution.
+pub fn solve<S: Solution>(input: &str) -> Result<(String, String), String> {
+    let model = S::parse(input).map_err(|e| e.into().to_string())?;
+    let part_1 = S::part_1(&model).map_err(|e| e.into().to_string())?;
+    let part_2 = S::part_2(&model).map_err(|e| e.into().to_string())?;
+    Ok((part_1.to_string(), part_2.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        differential::{compare, Disagreement},
+        generate::Generator,
+    };
+
+    // A line per unit of size, each holding the seed
+    struct Lines;
+
+    impl Generator for Lines {
+        fn generate(size: usize, seed: u64) -> String {
+            format!("{seed}\n").repeat(size)
+        }
+    }
+
+    #[test]
+    fn compare_test() {
+        let lines = |input: &str| input.lines().count();
+        assert_eq!(Ok(()), compare::<Lines, _>(0..100, 5, lines, lines));
+        // Miscounts inputs of three or more lines, the first of which comes from seed 2
+        let miscount = |input: &str| lines(input).min(2);
+        let disagreement = compare::<Lines, _>(0..100, 5, lines, miscount).unwrap_err();
+        assert_eq!(
+            Disagreement {
+                seed: 2,
+                size: 3,
+                input: "2\n2\n2\n".to_string(),
+                reference: 3,
+                candidate: 2,
+            },
+            disagreement
+        );
+        assert_eq!(
+            "solvers disagree on the input of size 3 generated from seed 2\n\
+             reference: 3\ncandidate: 2\ninput:\n2\n2\n2\n",
+            disagreement.to_string()
+        );
+    }
+}
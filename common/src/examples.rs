@@ -0,0 +1,253 @@
+//! Example inputs kept in a day's `examples` directory, each carrying the answers it should give.
+//!
+//! An example file starts with a header giving the expected answers, one line per part, ended by
+//! a `---` line. Everything after that is the input. A part left out of the header is not
+//! checked, for inputs that only make sense for one of the parts.
+//!
+//! ```text
+//! part 1: 142
+//! part 2: 142
+//! ---
+//! 1abc2
+//! pqr3stu8vwx
+//! ```
+//!
+//! [`examples_test!`](crate::examples_test) turns every example file of a day into a test of its
+//! own.
+
+use std::{fs, io, path::Path};
+
+use crate::Solution;
+
+const SEPARATOR: &str = "---";
+
+/// An input along with the answers it is expected to give
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    pub input: String,
+}
+
+impl Example {
+    /// Splits the contents of an example file into its header and input
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let (mut part_1, mut part_2) = (None, None);
+        let mut lines = contents.split_inclusive('\n');
+        for line in lines.by_ref() {
+            let line = line.trim_end();
+            if line == SEPARATOR {
+                let input = lines.collect();
+                return Ok(Example {
+                    part_1,
+                    part_2,
+                    input,
+                });
+            }
+            let (part, answer) = match line.split_once(':') {
+                Some(("part 1", answer)) => (&mut part_1, answer),
+                Some(("part 2", answer)) => (&mut part_2, answer),
+                _ => {
+                    return Err(format!(
+                        "expected \"part 1:\" or \"part 2:\", found {line:?}"
+                    ))
+                }
+            };
+            if part.replace(answer.trim().to_string()).is_some() {
+                return Err(format!("{line:?} repeats the answer to a part"));
+            }
+        }
+        Err(format!("header is not ended by a {SEPARATOR:?} line"))
+    }
+
+    /// Solves the example, describing every way it went other than expected
+    pub fn check<S: Solution>(&self) -> Vec<String> {
+        let model = match S::parse(&self.input) {
+            Ok(model) => model,
+            Err(e) => return vec![format!("parsing failed: {}", e.into())],
+        };
+        let compare = |part, expected: &String, answer: Result<S::Answer, S::Error>| match answer {
+            Ok(answer) if answer.to_string() == *expected => None,
+            Ok(answer) => Some(format!("{part} is {answer}, expected {expected}")),
+            Err(e) => Some(format!("{part} failed: {}", e.into())),
+        };
+        // Parts are only solved when there is an answer to compare against
+        let part_1 = self
+            .part_1
+            .as_ref()
+            .and_then(|expected| compare("part 1", expected, S::part_1(&model)));
+        let part_2 = self
+            .part_2
+            .as_ref()
+            .and_then(|expected| compare("part 2", expected, S::part_2(&model)));
+        part_1.into_iter().chain(part_2).collect()
+    }
+}
+
+/// Checks the contents of an example file against `S`, returning a line for each problem found
+pub fn check<S: Solution>(contents: &str) -> Vec<String> {
+    match Example::parse(contents) {
+        Ok(example) => example.check::<S>(),
+        Err(problem) => vec![problem],
+    }
+}
+
+/// Writes a test for every `.txt` file in `dir` to `out`, for [`examples_test!`] to include. The
+/// tests are named after the files, with characters that cannot be in a name replaced by `_`.
+///
+/// This is meant to be called from a build script, and has Cargo run the script again whenever
+/// a file in `dir` changes, so that adding an example is all it takes to test it.
+///
+/// [`examples_test!`]: crate::examples_test
+pub fn write_tests(dir: impl AsRef<Path>, out: impl AsRef<Path>) -> io::Result<()> {
+    let dir = dir.as_ref().canonicalize()?;
+    println!("cargo:rerun-if-changed={}", dir.display());
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "txt") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    let mut tests = String::new();
+    for path in &paths {
+        let name = test_name(&path.file_stem().unwrap_or_default().to_string_lossy());
+        tests += &format!("#[test]\nfn {name}() {{\n    check(include_str!({path:?}));\n}}\n");
+    }
+    // A directory without examples is a problem as well
+    if paths.is_empty() {
+        let problem = format!("{} holds no examples", dir.display());
+        tests += &format!("#[test]\nfn no_examples() {{\n    panic!(\"{{}}\", {problem:?});\n}}\n");
+    }
+    fs::write(out, tests)
+}
+
+fn test_name(file_stem: &str) -> String {
+    let name: String = file_stem
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => name,
+        _ => format!("example_{name}"),
+    }
+}
+
+/// Includes the tests [`write_tests`] wrote for the crate's examples in a module named
+/// `examples_test`, checking each example file against the given solution. The crate's build
+/// script has to write them to `examples_test.rs` in `OUT_DIR`.
+///
+/// ```ignore
+/// common::examples_test!(Day1);
+/// ```
+#[macro_export]
+macro_rules! examples_test {
+    ($solution:ty) => {
+        mod examples_test {
+            #[allow(unused_imports)]
+            use super::*;
+
+            fn check(contents: &str) {
+                let problems = $crate::examples::check::<$solution>(contents);
+                assert!(problems.is_empty(), "{}", problems.join("\n"));
+            }
+
+            include!(concat!(env!("OUT_DIR"), "/examples_test.rs"));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::Infallible, fs, io};
+
+    use crate::{
+        examples::{check, write_tests, Example},
+        Solution,
+    };
+
+    // Answers with the number of lines and the length of the longest line
+    struct Lines;
+
+    impl Solution for Lines {
+        const DAY: u8 = 1;
+
+        type Model = Vec<String>;
+        type Answer = usize;
+        type Error = Infallible;
+
+        fn parse(input: &str) -> Result<Self::Model, Infallible> {
+            Ok(input.lines().map(String::from).collect())
+        }
+
+        fn part_1(model: &Self::Model) -> Result<usize, Infallible> {
+            Ok(model.len())
+        }
+
+        fn part_2(model: &Self::Model) -> Result<usize, Infallible> {
+            Ok(model.iter().map(String::len).max().unwrap_or_default())
+        }
+    }
+
+    #[test]
+    fn parse_test() {
+        let example = Example::parse("part 2:  7\n---\nabc\n---\n").unwrap();
+        assert_eq!(
+            (None, Some("7")),
+            (example.part_1, example.part_2.as_deref())
+        );
+        assert_eq!("abc\n---\n", example.input);
+        let example = Example::parse("---\r\n").unwrap();
+        assert_eq!(
+            (None, None, ""),
+            (example.part_1, example.part_2, &*example.input)
+        );
+        for (contents, problem) in [
+            (
+                "part 1: 1\nabc\n",
+                "expected \"part 1:\" or \"part 2:\", found \"abc\"",
+            ),
+            (
+                "part 1: 1\npart 1: 2\n---\n",
+                "\"part 1: 2\" repeats the answer to a part",
+            ),
+            ("part 1: 1\n", "header is not ended by a \"---\" line"),
+        ] {
+            assert_eq!(Err(problem.to_string()), Example::parse(contents));
+        }
+    }
+
+    #[test]
+    fn check_test() {
+        assert!(check::<Lines>("part 1: 2\npart 2: 5\n---\nab\nabcde\n").is_empty());
+        assert_eq!(
+            vec!["part 2 is 2, expected 1"],
+            check::<Lines>("part 2: 1\n---\nab\n")
+        );
+        assert_eq!(
+            vec!["expected \"part 1:\" or \"part 2:\", found \"1\""],
+            check::<Lines>("1\n")
+        );
+    }
+
+    #[test]
+    fn write_tests_test() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let out = dir.path().join("examples_test.rs");
+        write_tests(dir.path(), &out)?;
+        assert!(fs::read_to_string(&out)?.contains("fn no_examples() {"));
+        for name in ["single-race.txt", "2.txt", "notes.md"] {
+            fs::write(dir.path().join(name), "---\n")?;
+        }
+        write_tests(dir.path(), &out)?;
+        let tests = fs::read_to_string(&out)?;
+        let path = dir.path().canonicalize()?.join("single-race.txt");
+        assert!(tests.contains(&format!(
+            "fn single_race() {{\n    check(include_str!({path:?}));"
+        )));
+        assert!(tests.contains("fn example_2() {"));
+        assert_eq!(2, tests.matches("#[test]").count());
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+//! Deterministic generation of synthetic puzzle inputs for stress tests and benchmarks
+
+use std::ops::Range;
+
+/// Produces valid puzzle inputs of a requested size. The same size and seed always produce
+/// the same input.
+pub trait Generator {
+    fn generate(size: usize, seed: u64) -> String;
+}
+
+/// A small SplitMix64 random number generator.
+///
+/// It is implemented here rather than taken from a crate so that the inputs generated from a
+/// seed never change between dependency updates.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value within `range`, which must not be empty
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    /// An index below `len`, which must not be zero
+    pub fn below(&mut self, len: usize) -> usize {
+        self.range(0..len as u64) as usize
+    }
+
+    /// True one time in `n` on average
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.range(0..n) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generate::Rng;
+
+    #[test]
+    fn rng_test() {
+        let values = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.range(10..20)).collect::<Vec<_>>()
+        };
+        assert_eq!(values(7), values(7));
+        assert_ne!(values(7), values(8));
+        assert!(values(7).iter().all(|value| (10..20).contains(value)));
+        let mut items: Vec<u32> = (0..50).collect();
+        Rng::new(1).shuffle(&mut items);
+        assert_ne!((0..50).collect::<Vec<_>>(), items);
+        items.sort();
+        assert_eq!((0..50).collect::<Vec<_>>(), items);
+    }
+}
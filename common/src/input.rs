@@ -0,0 +1,89 @@
+//! Cleaning up puzzle input before it reaches a parser, so that input saved by editors on any
+//! platform parses the same
+
+use std::borrow::Cow;
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// Puts input in the shape the parsers expect: no byte order mark, `\n` line endings, no
+/// whitespace at the end of a line and a single newline after the last line that is not blank.
+/// Input already in that shape is borrowed rather than copied.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix(BYTE_ORDER_MARK).unwrap_or(input);
+    if is_normalized(input) {
+        return Cow::Borrowed(input);
+    }
+    let mut normalized = String::with_capacity(input.len() + 1);
+    for line in input.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    // Blank lines at the end are left out, along with the final newline when nothing is left
+    normalized.truncate(normalized.trim_end().len());
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    Cow::Owned(normalized)
+}
+
+fn is_normalized(input: &str) -> bool {
+    let Some(body) = input.strip_suffix('\n') else {
+        return input.is_empty();
+    };
+    !body.is_empty()
+        && !body.ends_with('\n')
+        && body.split('\n').all(|line| line.trim_end() == line)
+}
+
+/// Normalizes a single line of input with the same rules as [`normalize`], given its 1-based
+/// line number and its contents without the `\n`
+pub fn normalize_line(number: usize, line: &str) -> &str {
+    let line = match number {
+        1 => line.strip_prefix(BYTE_ORDER_MARK).unwrap_or(line),
+        _ => line,
+    };
+    line.trim_end()
+}
+
+/// Saves input the way a Windows editor would, with a byte order mark and CRLF line endings, for
+/// tests checking that such input parses the same
+#[cfg(any(test, feature = "test-support"))]
+pub fn windows(input: &str) -> String {
+    format!("{BYTE_ORDER_MARK}{}", input.replace('\n', "\r\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::input::{normalize, normalize_line};
+
+    #[test]
+    fn normalize_test() {
+        for input in ["", "a\n", "a\n\nb\n", "  indented\n"] {
+            assert!(matches!(normalize(input), Cow::Borrowed(_)), "{input:?}");
+            assert_eq!(input, normalize(input));
+        }
+        let cases = [
+            ("\u{feff}a\r\nb\r\n", "a\nb\n"),
+            ("a\nb", "a\nb\n"),
+            ("a  \nb\t\n", "a\nb\n"),
+            ("a\n\n\n  \n", "a\n"),
+            ("a\r\n\r\nb", "a\n\nb\n"),
+            ("\n \r\n", ""),
+            ("\u{feff}", ""),
+            // Only carriage returns ending a line are part of the line ending
+            ("a\rb\n", "a\rb\n"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(expected, normalize(input), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn normalize_line_test() {
+        assert_eq!("a", normalize_line(1, "\u{feff}a\r"));
+        assert_eq!("\u{feff}a", normalize_line(2, "\u{feff}a"));
+        assert_eq!(" a", normalize_line(3, " a \t"));
+    }
+}
@@ -0,0 +1,13 @@
+//! Code shared between the solutions for each day
+
+mod diagnostic;
+pub mod differential;
+pub mod examples;
+pub mod generate;
+pub mod input;
+pub mod parse;
+mod solution;
+pub mod stream;
+
+pub use diagnostic::{line_number, Diagnostic};
+pub use solution::Solution;
@@ -0,0 +1,304 @@
+//! Parser combinators shared by every day's input format, built on [`nom`].
+//!
+//! Errors keep the slice of input they failed at so that [`parse_all`] can turn them into a
+//! [`Diagnostic`] pointing at the offending token.
+
+use std::{any::Any, fmt::Display, num::ParseIntError, str::FromStr};
+
+use nom::{
+    bytes::complete::{tag, take_till1},
+    character::complete::{alphanumeric1, line_ending, not_line_ending, satisfy, space0, space1},
+    combinator::{cut, map_res, peek, verify},
+    error::{ContextError, ErrorKind, FromExternalError, ParseError},
+    multi::{separated_list0, separated_list1},
+    sequence::{delimited, preceded, tuple},
+    Parser,
+};
+
+use crate::Diagnostic;
+
+/// Why and where a parser failed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error<'a> {
+    /// The offending slice of input, empty when something was expected but missing
+    pub token: &'a str,
+    pub message: Option<String>,
+    pub label: String,
+    /// Kept so that callers can tell numbers that failed to parse from other errors
+    pub source: Option<ParseIntError>,
+}
+
+pub type IResult<'a, O> = nom::IResult<&'a str, O, Error<'a>>;
+
+impl<'a> Error<'a> {
+    pub fn new(token: &'a str, message: impl Into<String>, label: impl Into<String>) -> Self {
+        Error {
+            token,
+            message: Some(message.into()),
+            label: label.into(),
+            source: None,
+        }
+    }
+
+    /// An unrecoverable error, which stops alternatives and repetitions from backtracking
+    pub fn failure<O>(
+        token: &'a str,
+        message: impl Into<String>,
+        label: impl Into<String>,
+    ) -> IResult<'a, O> {
+        Err(nom::Err::Failure(Error::new(token, message, label)))
+    }
+
+    pub fn into_diagnostic(self, source: &str) -> Diagnostic {
+        let message = self
+            .message
+            .unwrap_or_else(|| "input was not properly formatted".to_string());
+        let diagnostic = Diagnostic::new(source, self.token, message, self.label);
+        match self.source {
+            Some(error) => diagnostic.with_source(error),
+            None => diagnostic,
+        }
+    }
+}
+
+// The token at the start of the input, which is what gets pointed at when a parser fails there.
+// This is a run of letters and digits, or otherwise the single character found there.
+fn next_word(input: &str) -> &str {
+    let end = match input.chars().next() {
+        Some(c) if c.is_alphanumeric() => input
+            .find(|c: char| !c.is_alphanumeric())
+            .unwrap_or(input.len()),
+        Some(c) if !c.is_whitespace() => c.len_utf8(),
+        _ => 0,
+    };
+    &input[..end]
+}
+
+/// Everything up to the end of the current line
+pub fn rest_of_line(input: &str) -> &str {
+    let end = input.find(['\r', '\n']).unwrap_or(input.len());
+    &input[..end]
+}
+
+impl<'a> ParseError<&'a str> for Error<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        let label = match kind {
+            ErrorKind::Digit | ErrorKind::Satisfy => "expected a number",
+            ErrorKind::CrLf => "expected a line ending",
+            ErrorKind::Space => "expected whitespace",
+            ErrorKind::Eof => "unexpected trailing input",
+            _ => "unexpected input",
+        };
+        Error {
+            token: next_word(input),
+            message: None,
+            label: label.to_string(),
+            source: None,
+        }
+    }
+
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: &'a str, c: char) -> Self {
+        Error {
+            token: &input[..0],
+            message: None,
+            label: format!("expected '{c}'"),
+            source: None,
+        }
+    }
+}
+
+impl<'a> ContextError<&'a str> for Error<'a> {
+    // The innermost context is the most specific, so outer ones never replace it
+    fn add_context(_: &'a str, context: &'static str, mut other: Self) -> Self {
+        other.message.get_or_insert_with(|| context.to_string());
+        other
+    }
+}
+
+impl<'a, E: Display + 'static> FromExternalError<&'a str, E> for Error<'a> {
+    fn from_external_error(input: &'a str, _: ErrorKind, e: E) -> Self {
+        Error {
+            token: next_word(input),
+            message: None,
+            label: e.to_string(),
+            source: (&e as &dyn Any).downcast_ref::<ParseIntError>().cloned(),
+        }
+    }
+}
+
+/// Runs a parser over the whole source, which must be consumed completely
+pub fn parse_all<'a, O>(
+    source: &'a str,
+    mut parser: impl Parser<&'a str, O, Error<'a>>,
+) -> Result<O, Diagnostic> {
+    match parser.parse(source) {
+        Ok(("", output)) => Ok(output),
+        Ok((rest, _)) => Err(Diagnostic::new(
+            source,
+            rest_of_line(rest),
+            "input was not properly formatted",
+            "unexpected trailing input",
+        )),
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => {
+            Err(error.into_diagnostic(source))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(Diagnostic::new(
+            source,
+            &source[source.len()..],
+            "input was not properly formatted",
+            "unexpected end of input",
+        )),
+    }
+}
+
+/// Matches `expected` exactly, naming it in the error when it is missing
+pub fn token<'a>(expected: &'static str) -> impl FnMut(&'a str) -> IResult<'a, &'a str> {
+    move |input| {
+        tag(expected)(input).map_err(|e: nom::Err<Error<'a>>| {
+            e.map(|mut error| {
+                error.label = format!("expected \"{expected}\"");
+                error
+            })
+        })
+    }
+}
+
+/// An unsigned integer such as `42`.
+///
+/// Anything alphanumeric directly following the leading digit is treated as part of the
+/// number, so `4x8` fails as a whole instead of parsing `4` and leaving `x8` behind.
+pub fn integer<'a, T>(input: &'a str) -> IResult<'a, T>
+where
+    T: FromStr,
+    T::Err: Display + 'static,
+{
+    preceded(
+        peek(satisfy(|c| c.is_ascii_digit())),
+        cut(map_res(alphanumeric1, str::parse)),
+    )(input)
+}
+
+/// Integers separated by spaces or tabs on a single line, such as `41 48  83 86`. Spaces
+/// around the list are consumed as well.
+pub fn integers<'a, T>(input: &'a str) -> IResult<'a, Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display + 'static,
+{
+    delimited(space0, separated_list0(space1, integer), space0)(input)
+}
+
+/// A `Label N: payload` record such as `Game 3: ...` or `Card  12: ...`, returning the record
+/// number and the payload
+pub fn labeled_record<'a, T, O>(
+    label: &'static str,
+    payload: impl Parser<&'a str, O, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, (T, O)>
+where
+    T: FromStr,
+    T::Err: Display + 'static,
+{
+    let mut record = tuple((token(label), space1, integer, token(":"), space0, payload));
+    move |input| {
+        let (rest, (_, _, number, _, _, payload)) = record.parse(input)?;
+        Ok((rest, (number, payload)))
+    }
+}
+
+/// A line containing only spaces or tabs, following the line ending of the previous line
+pub fn blank_line(input: &str) -> IResult<'_, &str> {
+    let (rest, (_, blank, _)) = tuple((line_ending, space0, line_ending))(input)?;
+    Ok((rest, blank))
+}
+
+/// Sections of lines separated by blank lines, such as the maps of an almanac
+pub fn sections<'a, O>(
+    section: impl Parser<&'a str, O, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    separated_list1(blank_line, section)
+}
+
+/// A rectangular grid of characters, returned as its rows. The grid ends at a blank line.
+pub fn grid(input: &str) -> IResult<'_, Vec<&str>> {
+    let (rest, rows) = separated_list1(
+        line_ending,
+        verify(not_line_ending, |row: &str| !row.trim().is_empty()),
+    )(input)?;
+    let width = rows[0].chars().count();
+    if let Some(row) = rows.iter().find(|row| row.chars().count() != width) {
+        return Error::failure(
+            row,
+            "grid rows must all be the same width",
+            format!("expected {width} characters"),
+        );
+    }
+    Ok((rest, rows))
+}
+
+/// A single word of letters, digits or dashes such as `seed-to-soil`
+pub fn word(input: &str) -> IResult<'_, &str> {
+    take_till1(|c: char| !(c.is_alphanumeric() || c == '-'))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::{IntErrorKind, ParseIntError};
+
+    use nom::{character::complete::char, sequence::separated_pair};
+
+    use crate::parse::{grid, integers, labeled_record, parse_all, sections};
+
+    #[test]
+    fn labeled_record_test() {
+        let card = parse_all(
+            "Card  12: 41 48 | 83 86",
+            labeled_record("Card", separated_pair(integers, char('|'), integers::<u8>)),
+        );
+        assert_eq!(Ok((12u8, (vec![41, 48], vec![83, 86]))), card);
+        let error = parse_all("Game x: 1", labeled_record::<u8, _>("Game", integers::<u8>));
+        assert_eq!(
+            (1, 5..6),
+            (
+                error.clone().unwrap_err().line_number,
+                error.unwrap_err().span
+            )
+        );
+    }
+
+    #[test]
+    fn integers_test() {
+        assert_eq!(Ok(vec![7u64, 15, 30]), parse_all("  7  15\t30", integers));
+        let error = parse_all("41 4x8 9", integers::<u8>).unwrap_err();
+        assert_eq!(
+            (3..6, "invalid digit found in string"),
+            (error.span, error.label.as_str())
+        );
+        let error = parse_all("1 300", integers::<u8>).unwrap_err();
+        assert_eq!("number too large to fit in target type", error.label);
+        assert_eq!(
+            Some(&IntErrorKind::PosOverflow),
+            error.source.as_ref().map(ParseIntError::kind)
+        );
+    }
+
+    #[test]
+    fn sections_test() {
+        let input = "a\nb\n\nc\n  \nd";
+        let parsed = parse_all(input, sections(grid));
+        assert_eq!(Ok(vec![vec!["a", "b"], vec!["c"], vec!["d"]]), parsed);
+    }
+
+    #[test]
+    fn grid_test() {
+        assert_eq!(Ok(vec!["..*", "1.."]), parse_all("..*\r\n1..", grid));
+        let error = parse_all("...\n....\n", grid).unwrap_err();
+        assert_eq!(
+            (2, "expected 3 characters"),
+            (error.line_number, error.label.as_str())
+        );
+    }
+}
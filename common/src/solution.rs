@@ -0,0 +1,17 @@
+use std::{error::Error, fmt::Display};
+
+/// A day's puzzle split into the phases the runner drives: parsing the input into a model,
+/// then solving each part from that model
+pub trait Solution {
+    /// Day of December the puzzle was released on
+    const DAY: u8;
+
+    /// The puzzle input in parsed form
+    type Model;
+    type Answer: Display;
+    type Error: Into<Box<dyn Error + Send + Sync>>;
+
+    fn parse(input: &str) -> Result<Self::Model, Self::Error>;
+    fn part_1(model: &Self::Model) -> Result<Self::Answer, Self::Error>;
+    fn part_2(model: &Self::Model) -> Result<Self::Answer, Self::Error>;
+}
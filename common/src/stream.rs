@@ -0,0 +1,154 @@
+//! Line by line reading of puzzle input, for solutions that never need the whole input at once
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+};
+
+use crate::input::normalize_line;
+
+/// Calls `f` with the 1-based number and contents of every line, normalized the way
+/// [`crate::input::normalize`] does. Blank lines are only passed on once a line that is not blank
+/// follows them, so those at the end of the input are skipped.
+///
+/// A single buffer is reused for every line so memory use does not grow with the input.
+pub fn for_each_line<E: From<io::Error>>(
+    mut reader: impl BufRead,
+    mut f: impl FnMut(usize, &str) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut line = String::new();
+    let mut number = 0;
+    let mut blank = 0;
+    while reader.read_line(&mut line)? > 0 {
+        number += 1;
+        let trimmed = line.strip_suffix('\n').unwrap_or(&line);
+        match normalize_line(number, trimmed) {
+            "" => blank += 1,
+            trimmed => {
+                for blank_number in number - blank..number {
+                    f(blank_number, "")?;
+                }
+                blank = 0;
+                f(number, trimmed)?;
+            }
+        }
+        line.clear();
+    }
+    Ok(())
+}
+
+/// Maps every line with `map` and hands the results to `consume` in input order.
+///
+/// With the `parallel` feature lines are read in batches which are mapped on the rayon thread
+/// pool, so memory use stays bounded by the batch size. Either way the first error in input
+/// order is the one returned.
+pub fn map_lines<T, E>(
+    reader: impl BufRead,
+    map: impl Fn(usize, &str) -> Result<T, E> + Sync,
+    mut consume: impl FnMut(T) -> Result<(), E>,
+) -> Result<(), E>
+where
+    T: Send,
+    E: From<io::Error> + Send,
+{
+    #[cfg(not(feature = "parallel"))]
+    {
+        for_each_line(reader, |number, line| consume(map(number, line)?))
+    }
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+
+        const BATCH_SIZE: usize = 4096;
+        let mut batch: Vec<String> = Vec::with_capacity(BATCH_SIZE);
+        let mut first_number = 1;
+        let mut flush = |batch: &mut Vec<String>, first_number: &mut usize| {
+            let results: Vec<Result<T, E>> = batch
+                .par_iter()
+                .enumerate()
+                .map(|(i, line)| map(*first_number + i, line))
+                .collect();
+            *first_number += batch.len();
+            batch.clear();
+            results.into_iter().try_for_each(|result| consume(result?))
+        };
+        for_each_line(reader, |_, line| {
+            batch.push(line.to_string());
+            if batch.len() == BATCH_SIZE {
+                flush(&mut batch, &mut first_number)?;
+            }
+            Ok::<_, E>(())
+        })?;
+        flush(&mut batch, &mut first_number)
+    }
+}
+
+/// Opens the input named by the first command line argument, where `-` means stdin, falling
+/// back to the bundled puzzle input when no argument is given
+pub fn open_input(bundled: &'static str) -> io::Result<Box<dyn BufRead>> {
+    Ok(match std::env::args().nth(1).as_deref() {
+        Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+        None => Box::new(bundled.as_bytes()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, BufReader};
+
+    use crate::stream::{for_each_line, map_lines};
+
+    #[test]
+    fn for_each_line_test() -> io::Result<()> {
+        // A tiny buffer makes lines span several reads
+        let input = "\u{feff}first line\r\nsecond \n\n\t\nlast\n\r\n\n";
+        let reader = BufReader::with_capacity(3, input.as_bytes());
+        let mut lines = Vec::new();
+        for_each_line(reader, |number, line| {
+            lines.push((number, line.to_string()));
+            Ok::<_, io::Error>(())
+        })?;
+        let expected = [
+            (1, "first line"),
+            (2, "second"),
+            (3, ""),
+            (4, ""),
+            (5, "last"),
+        ];
+        assert_eq!(expected.map(|(n, l)| (n, l.to_string())).to_vec(), lines);
+        Ok(())
+    }
+
+    #[test]
+    fn map_lines_test() {
+        // Enough lines to span several batches when running in parallel
+        let input: String = (1..=10_000).map(|i| format!("{i}\n")).collect();
+        let mut consumed = Vec::new();
+        let result = map_lines(
+            input.as_bytes(),
+            |number, line| {
+                let value: usize = line.parse().unwrap();
+                assert_eq!(number, value);
+                Ok::<_, io::Error>(value)
+            },
+            |value| {
+                consumed.push(value);
+                Ok(())
+            },
+        );
+        assert!(result.is_ok());
+        assert_eq!((1..=10_000).collect::<Vec<_>>(), consumed);
+        // Errors are reported for the earliest failing line regardless of scheduling
+        let error = map_lines(
+            input.as_bytes(),
+            |number, _| match number {
+                5000 | 9000 => Err(io::Error::other(number.to_string())),
+                _ => Ok(number),
+            },
+            |_| Ok(()),
+        )
+        .unwrap_err();
+        assert_eq!("5000", error.to_string());
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    error::Error as StdError,
+    fmt::{self, Display, Formatter},
+    io,
+};
+
+use common::Diagnostic;
+
+/// Why a calibration document could not be solved
+#[derive(Debug)]
+pub enum Error {
+    /// A line without a single digit to make a calibration value from
+    NoDigits(Diagnostic),
+    /// A line whose digits are all spelled out in letters, which part 1 does not read
+    OnlySpelledDigits { line_number: usize },
+    /// The document could not be read
+    Io(io::Error),
+}
+
+impl Error {
+    /// Where in the input the error is, for errors that point at a line
+    pub fn diagnostic(&self) -> Option<&Diagnostic> {
+        match self {
+            Error::NoDigits(diagnostic) => Some(diagnostic),
+            Error::OnlySpelledDigits { .. } | Error::Io(_) => None,
+        }
+    }
+
+    pub(crate) fn with_line_number(self, line_number: usize) -> Self {
+        match self {
+            Error::NoDigits(diagnostic) => {
+                Error::NoDigits(diagnostic.with_line_number(line_number))
+            }
+            error => error,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoDigits(diagnostic) => write!(f, "{diagnostic}"),
+            Error::OnlySpelledDigits { line_number } => write!(
+                f,
+                "calibration line {line_number} only has digits spelled out in letters"
+            ),
+            Error::Io(_) => write!(f, "could not read the calibration document"),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            // The diagnostic is what gets displayed, so its source comes next
+            Error::NoDigits(diagnostic) => diagnostic.source(),
+            Error::OnlySpelledDigits { .. } => None,
+            Error::Io(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
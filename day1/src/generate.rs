@@ -0,0 +1,51 @@
+use common::generate::{Generator, Rng};
+
+use crate::{Day1, ENGLISH_DIGITS};
+
+// Lines mix letters, digits and spelled out digits like the puzzle's. Every line has at least
+// one digit, as part 1 cannot read a line without one.
+impl Generator for Day1 {
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        let mut input = String::new();
+        for _ in 0..size {
+            let tokens = 1 + rng.below(6);
+            let digit_at = rng.below(tokens);
+            for i in 0..tokens {
+                match rng.below(3) {
+                    _ if i == digit_at => input.push(char::from(b'0' + rng.range(1..10) as u8)),
+                    0 => input.push(char::from(b'0' + rng.range(0..10) as u8)),
+                    1 => input.push_str(rng.pick(&ENGLISH_DIGITS).0),
+                    _ => {
+                        for _ in 0..rng.range(1..5) {
+                            input.push(char::from(b'a' + rng.range(0..26) as u8));
+                        }
+                    }
+                }
+            }
+            input.push('\n');
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use common::{generate::Generator, Solution};
+
+    use crate::{solve, Day1};
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = Day1::generate(500, 1);
+        assert_eq!(input, Day1::generate(500, 1));
+        assert_ne!(input, Day1::generate(500, 2));
+        let model = Day1::parse(&input)?;
+        assert_eq!(500, model.len());
+        let answers = (Day1::part_1(&model)?, Day1::part_2(&model)?);
+        let (part_1, part_2) = solve(input.as_bytes())?;
+        assert_eq!(answers, (part_1?, part_2));
+        Ok(())
+    }
+}
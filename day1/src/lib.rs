@@ -0,0 +1,295 @@
+use std::{io::BufRead, str::FromStr};
+
+use common::{input::normalize, stream::map_lines, Diagnostic, Solution};
+use once_cell::sync::Lazy;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod error;
+mod generate;
+
+pub use error::Error;
+
+static ENGLISH_DIGITS: Lazy<Vec<(&str, char)>> = Lazy::new(|| {
+    vec![
+        ("one", '1'),
+        ("two", '2'),
+        ("three", '3'),
+        ("four", '4'),
+        ("five", '5'),
+        ("six", '6'),
+        ("seven", '7'),
+        ("eight", '8'),
+        ("nine", '9'),
+    ]
+});
+
+/// The calibration values recovered from one line of the document
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Calibration {
+    /// Made of the first and last digit on the line, if it has any written in figures
+    pub value: Option<u32>,
+    /// Made of the first and last digit on the line, including ones spelled out in letters
+    pub spelled_value: u32,
+}
+
+impl FromStr for Calibration {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Neither part can read a line without a single digit, spelled out or not
+        let spelled_value = parse_calibration(s).ok_or_else(|| {
+            Error::NoDigits(Diagnostic::new(
+                s,
+                s,
+                "calibration line has no digits",
+                "expected at least one digit",
+            ))
+        })?;
+        Ok(Calibration {
+            value: calculate_calibration(s),
+            spelled_value,
+        })
+    }
+}
+
+/// Solves both parts in a single pass over the input so that it can be streamed from a pipe.
+///
+/// Part 1 fails on its own when a line only has digits spelled out in letters, which part 2
+/// can still read.
+pub fn solve(reader: impl BufRead) -> Result<(Result<u32, Error>, u32), Error> {
+    let (mut part_1, mut part_2) = (0, 0);
+    // The first line part 1 cannot read
+    let mut unreadable = None;
+    map_lines(
+        reader,
+        |number, line| {
+            line.parse::<Calibration>()
+                .map(|calibration| (number, calibration))
+                .map_err(|e| e.with_line_number(number))
+        },
+        |(number, calibration)| {
+            match calibration.value {
+                Some(value) => part_1 += value,
+                None => unreadable = unreadable.or(Some(number)),
+            }
+            part_2 += calibration.spelled_value;
+            Ok(())
+        },
+    )?;
+    let part_1 = match unreadable {
+        Some(line_number) => Err(Error::OnlySpelledDigits { line_number }),
+        None => Ok(part_1),
+    };
+    Ok((part_1, part_2))
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Model = Vec<Calibration>;
+    type Answer = u32;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        normalize(input)
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                line.parse::<Calibration>()
+                    .map_err(|e| e.with_line_number(i + 1))
+            })
+            .collect()
+    }
+
+    fn part_1(model: &Self::Model) -> Result<u32, Error> {
+        model
+            .iter()
+            .enumerate()
+            .try_fold(0, |sum, (i, calibration)| {
+                let value = calibration
+                    .value
+                    .ok_or(Error::OnlySpelledDigits { line_number: i + 1 })?;
+                Ok(sum + value)
+            })
+    }
+
+    fn part_2(model: &Self::Model) -> Result<u32, Error> {
+        Ok(model
+            .iter()
+            .map(|calibration| calibration.spelled_value)
+            .sum())
+    }
+}
+
+fn calculate_calibration(line: &str) -> Option<u32> {
+    // Fetch each ascii digit from the input line
+    let mut digits: Vec<char> = line.chars().filter(|c| c.is_ascii_digit()).collect();
+    let first = *digits.first()?;
+    // Per the example input, if the line only contains one digit, that digit is used twice
+    if digits.len() < 2 {
+        digits.push(first);
+    }
+    // Drain inner elements of digits
+    digits.drain(1..digits.len() - 1);
+    // The first and last digits make up a two digit number
+    let calibration = digits.into_iter().fold(0, |acc, digit| {
+        acc * 10 + digit.to_digit(10).unwrap_or_default()
+    });
+    Some(calibration)
+}
+
+fn parse_calibration(line: &str) -> Option<u32> {
+    let digits: String = [
+        find_first_digit(line.chars())?,
+        find_last_digit(line.chars().rev())?,
+    ]
+    .into_iter()
+    .collect();
+    digits.parse().ok()
+}
+
+fn find_first_digit(line: impl Iterator<Item = char>) -> Option<char> {
+    let mut acc = String::new();
+    for c in line {
+        if c.is_ascii_digit() {
+            return Some(c);
+        }
+        acc.push(c);
+        for (name, digit) in &*ENGLISH_DIGITS {
+            if acc.contains(name) {
+                return Some(*digit);
+            }
+        }
+    }
+    None
+}
+
+fn find_last_digit(line: impl Iterator<Item = char>) -> Option<char> {
+    let mut acc = String::new();
+    for c in line {
+        if c.is_ascii_digit() {
+            return Some(c);
+        }
+        acc.insert(0, c);
+        for (name, digit) in &*ENGLISH_DIGITS {
+            if acc.contains(name) {
+                return Some(*digit);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+
+    use common::{differential, input::windows, Solution};
+
+    use crate::{calculate_calibration, parse_calibration, solve, Calibration, Day1, Error};
+
+    common::examples_test!(Day1);
+
+    // The example of part 2, whose second line has no digit in figures
+    const SPELLED_EXAMPLE: &str = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n\
+                                   4nineeightseven2\nzoneight234\n7pqrstsixteen\n";
+
+    #[test]
+    fn get_digits_test() -> Result<()> {
+        assert_eq!(Some(12), calculate_calibration("1abc2"));
+        assert_eq!(Some(38), calculate_calibration("pqr3stu8vwx"));
+        assert_eq!(Some(15), calculate_calibration("a1b2c3d4e5f"));
+        assert_eq!(Some(77), calculate_calibration("treb7uchet"));
+        assert_eq!(None, calculate_calibration("eightwothree"));
+        let Err(Error::NoDigits(error)) = "trebuchet".parse::<Calibration>() else {
+            panic!("expected a line without digits to fail");
+        };
+        assert_eq!((1, 0..9), (error.column(), error.span));
+        Ok(())
+    }
+
+    #[test]
+    fn word_to_digit_test() -> Result<()> {
+        assert_eq!(Some(29), parse_calibration("two1nine"));
+        assert_eq!(Some(83), parse_calibration("eightwothree"));
+        assert_eq!(Some(13), parse_calibration("abcone2threexyz"));
+        assert_eq!(Some(24), parse_calibration("xtwone3four"));
+        assert_eq!(Some(42), parse_calibration("4nineeightseven2"));
+        assert_eq!(Some(14), parse_calibration("zoneight234"));
+        assert_eq!(Some(76), parse_calibration("7pqrstsixteen"));
+        Ok(())
+    }
+
+    #[test]
+    fn solve_test() -> Result<()> {
+        let (part_1, part_2) = solve(include_str!("test.txt").as_bytes())?;
+        assert_eq!((142, 142), (part_1?, part_2));
+        let (part_1, part_2) = solve(SPELLED_EXAMPLE.as_bytes())?;
+        assert!(matches!(
+            part_1,
+            Err(Error::OnlySpelledDigits { line_number: 2 })
+        ));
+        assert_eq!(281, part_2);
+        Ok(())
+    }
+
+    #[test]
+    fn solution_test() -> Result<()> {
+        let model = Day1::parse(include_str!("test.txt"))?;
+        assert_eq!((142, 142), (Day1::part_1(&model)?, Day1::part_2(&model)?));
+        let model = Day1::parse(SPELLED_EXAMPLE)?;
+        assert_eq!(281, Day1::part_2(&model)?);
+        assert!(matches!(
+            Day1::part_1(&model),
+            Err(Error::OnlySpelledDigits { line_number: 2 })
+        ));
+        let error = Day1::parse("1abc2\ntrebuchet\n").unwrap_err();
+        assert_eq!(Some(2), error.diagnostic().map(|d| d.line_number));
+        Ok(())
+    }
+
+    #[test]
+    fn windows_input_test() -> Result<()> {
+        let input = format!("{}\r\n\r\n", windows(include_str!("test.txt")));
+        let (part_1, part_2) = solve(input.as_bytes())?;
+        assert_eq!((142, 142), (part_1?, part_2));
+        let model = Day1::parse(&input)?;
+        assert_eq!((142, 142), (Day1::part_1(&model)?, Day1::part_2(&model)?));
+        Ok(())
+    }
+
+    // Looks for a digit, or a digit spelled out when that is allowed, at every position of each
+    // line. Generated lines always have a digit in figures.
+    fn reference(input: &str) -> Result<(String, String), String> {
+        const WORDS: [&str; 9] = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        let value = |line: &str, spelled: bool| {
+            let digits: Vec<u32> = (0..line.len())
+                .filter_map(|i| {
+                    let rest = &line[i..];
+                    let figure = rest.chars().next().and_then(|c| c.to_digit(10));
+                    let word = WORDS.iter().position(|word| rest.starts_with(word));
+                    figure.or(word.filter(|_| spelled).map(|i| i as u32 + 1))
+                })
+                .collect();
+            Some(digits.first()? * 10 + digits.last()?)
+        };
+        let (mut part_1, mut part_2) = (0, 0);
+        for (i, line) in input.lines().enumerate() {
+            part_1 += value(line, false).ok_or(format!("line {} has no digit", i + 1))?;
+            part_2 += value(line, true).ok_or(format!("line {} has no digit", i + 1))?;
+        }
+        Ok((part_1.to_string(), part_2.to_string()))
+    }
+
+    #[test]
+    fn differential_test() -> Result<()> {
+        differential::compare::<Day1, _>(0..200, 40, reference, differential::solve::<Day1>)?;
+        Ok(())
+    }
+}
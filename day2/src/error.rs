@@ -0,0 +1,81 @@
+use std::{
+    error::Error as StdError,
+    fmt::{self, Display, Formatter},
+    io,
+};
+
+use common::Diagnostic;
+
+/// Why a record of games could not be solved
+#[derive(Debug)]
+pub enum Error {
+    /// A line is not laid out like `Game 1: 3 blue, 4 red; 1 red`
+    Malformed(Diagnostic),
+    /// A game id or count of cubes is not a number, or too large
+    InvalidNumber(Diagnostic),
+    /// Cubes of a color other than red, green or blue
+    UnknownColor(Diagnostic),
+    /// The cubes of one color shown in a set add up to more than a `u8` holds
+    TooManyCubes(Diagnostic),
+    /// The record could not be read
+    Io(io::Error),
+}
+
+impl Error {
+    /// Where in the input the error is, for errors that point at a line
+    pub fn diagnostic(&self) -> Option<&Diagnostic> {
+        match self {
+            Error::Malformed(diagnostic)
+            | Error::InvalidNumber(diagnostic)
+            | Error::UnknownColor(diagnostic)
+            | Error::TooManyCubes(diagnostic) => Some(diagnostic),
+            Error::Io(_) => None,
+        }
+    }
+
+    pub(crate) fn with_line_number(self, line_number: usize) -> Self {
+        match self {
+            Error::Malformed(d) => Error::Malformed(d.with_line_number(line_number)),
+            Error::InvalidNumber(d) => Error::InvalidNumber(d.with_line_number(line_number)),
+            Error::UnknownColor(d) => Error::UnknownColor(d.with_line_number(line_number)),
+            Error::TooManyCubes(d) => Error::TooManyCubes(d.with_line_number(line_number)),
+            error => error,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.diagnostic() {
+            Some(diagnostic) => write!(f, "{diagnostic}"),
+            None => write!(f, "could not read the record of games"),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            // The diagnostic is what gets displayed, so its source comes next
+            error => error.diagnostic().and_then(StdError::source),
+        }
+    }
+}
+
+// Errors found by the parsers are about numbers when a number failed to parse, and otherwise
+// about the layout of the line
+impl From<Diagnostic> for Error {
+    fn from(diagnostic: Diagnostic) -> Self {
+        match diagnostic.source {
+            Some(_) => Error::InvalidNumber(diagnostic),
+            None => Error::Malformed(diagnostic),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
@@ -0,0 +1,53 @@
+use common::generate::{Generator, Rng};
+
+use crate::{CubeSet, Day2, Game};
+
+// Games draw up to 20 cubes of a color per set, so some exceed the part 1 bag and some do not
+impl Generator for Day2 {
+    fn generate(size: usize, seed: u64) -> String {
+        // Game ids are a single byte
+        let size = size.min(u8::MAX as usize);
+        let mut rng = Rng::new(seed);
+        let mut input = String::new();
+        for id in 1..=size as u8 {
+            let sets = (0..rng.range(1..7))
+                .map(|_| {
+                    let mut count = || match rng.below(3) {
+                        0 => 0,
+                        _ => rng.range(1..21) as u8,
+                    };
+                    CubeSet {
+                        red: count(),
+                        green: count(),
+                        blue: count(),
+                    }
+                })
+                .collect();
+            input.push_str(&Game { id, sets }.to_string());
+            input.push('\n');
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use common::{generate::Generator, Solution};
+
+    use crate::{solve, Day2};
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = Day2::generate(200, 1);
+        assert_eq!(input, Day2::generate(200, 1));
+        assert_ne!(input, Day2::generate(200, 2));
+        let model = Day2::parse(&input)?;
+        assert_eq!(200, model.len());
+        assert_eq!(255, Day2::parse(&Day2::generate(500, 1))?.len());
+        let answers = (Day2::part_1(&model)?, Day2::part_2(&model)?);
+        assert!(answers.0 > 0);
+        assert_eq!(answers, solve(input.as_bytes())?);
+        Ok(())
+    }
+}
@@ -0,0 +1,365 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    io::BufRead,
+    str::FromStr,
+};
+
+use common::{
+    input::normalize,
+    parse::{integer, labeled_record, parse_all, IResult},
+    stream::map_lines,
+    Diagnostic, Solution,
+};
+use nom::{
+    character::complete::{alpha1, char, space0, space1},
+    combinator::consumed,
+    error::context,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair, terminated},
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod error;
+mod generate;
+
+pub use error::Error;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CubeSet {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+/// The cubes the bag was loaded with in part 1
+pub const BAG: CubeSet = CubeSet {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+impl CubeSet {
+    pub fn is_superset(&self, other: &CubeSet) -> bool {
+        self.red >= other.red && self.green >= other.green && self.blue >= other.blue
+    }
+
+    pub fn make_superset(&mut self, other: &CubeSet) {
+        if self.red < other.red {
+            self.red = other.red
+        }
+        if self.green < other.green {
+            self.green = other.green
+        }
+        if self.blue < other.blue {
+            self.blue = other.blue
+        }
+    }
+}
+
+// A number of cubes of one color as written in a set, such as "3 blue"
+struct Draw<'a> {
+    token: &'a str,
+    count: u8,
+    color: &'a str,
+}
+
+// Parses comma separated color counts such as "3 blue, 4 red"
+fn draws(input: &str) -> IResult<'_, Vec<Draw<'_>>> {
+    let color_count = separated_pair(context("invalid cube count", integer), space1, alpha1);
+    let (rest, counts) =
+        separated_list1(char(','), preceded(space0, consumed(color_count)))(input)?;
+    let draws = counts
+        .into_iter()
+        .map(|(token, (count, color))| Draw {
+            token,
+            count,
+            color,
+        })
+        .collect();
+    Ok((rest, draws))
+}
+
+impl CubeSet {
+    // Adds up the draws of a set parsed from `source`. A color may be listed more than once, in
+    // which case the counts add up.
+    fn from_draws(source: &str, draws: &[Draw]) -> Result<Self, Error> {
+        let mut cube_set = CubeSet::default();
+        for draw in draws {
+            let total = match draw.color {
+                "red" => &mut cube_set.red,
+                "green" => &mut cube_set.green,
+                "blue" => &mut cube_set.blue,
+                _ => {
+                    return Err(Error::UnknownColor(Diagnostic::new(
+                        source,
+                        draw.color,
+                        "color_name was not a valid value",
+                        "expected red, green or blue",
+                    )))
+                }
+            };
+            *total = total.checked_add(draw.count).ok_or_else(|| {
+                Error::TooManyCubes(Diagnostic::new(
+                    source,
+                    draw.token,
+                    "too many cubes of one color",
+                    "total does not fit in a u8",
+                ))
+            })?;
+        }
+        Ok(cube_set)
+    }
+}
+
+impl FromStr for CubeSet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let draws = parse_all(s, terminated(draws, space0))?;
+        CubeSet::from_draws(s, &draws)
+    }
+}
+
+// Writes the colors that were drawn in red, green, blue order such as "4 red, 3 blue". A set
+// without any cubes is written as "0 red" as the format has no way to draw nothing.
+impl Display for CubeSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let counts = [
+            (self.red, "red"),
+            (self.green, "green"),
+            (self.blue, "blue"),
+        ];
+        let mut drawn = counts.iter().filter(|(count, _)| *count > 0).peekable();
+        if drawn.peek().is_none() {
+            return write!(f, "0 red");
+        }
+        for (i, (count, name)) in drawn.enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{count} {name}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Game {
+    pub id: u8,
+    pub sets: Vec<CubeSet>,
+}
+
+impl Game {
+    /// Whether every set shown could have been drawn from the given bag
+    pub fn is_possible(&self, bag: &CubeSet) -> bool {
+        self.sets.iter().all(|set| bag.is_superset(set))
+    }
+
+    pub fn get_power(&self) -> u32 {
+        let lowest_set = self.sets.iter().fold(CubeSet::default(), |mut acc, set| {
+            acc.make_superset(set);
+            acc
+        });
+        lowest_set.red as u32 * lowest_set.green as u32 * lowest_set.blue as u32
+    }
+}
+
+impl FromStr for Game {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let game = labeled_record("Game", separated_list1(char(';'), draws));
+        let (id, sets) = parse_all(
+            s,
+            context(
+                "game string was not properly formatted",
+                terminated(game, space0),
+            ),
+        )?;
+        let sets = sets
+            .iter()
+            .map(|draws| CubeSet::from_draws(s, draws))
+            .collect::<Result<_, _>>()?;
+        Ok(Game { id, sets })
+    }
+}
+
+impl Display for Game {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
+        for (i, set) in self.sets.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{set}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Solves both parts in a single pass over the input so that it can be streamed from a pipe
+pub fn solve(reader: impl BufRead) -> Result<(u64, u64), Error> {
+    let mut id_sum = 0;
+    let mut lowest_power_sum = 0;
+    map_lines(
+        reader,
+        |number, line| {
+            let game: Game = line
+                .parse()
+                .map_err(|e: Error| e.with_line_number(number))?;
+            let possible = game.is_possible(&BAG);
+            Ok::<_, Error>((possible.then_some(game.id as u32), game.get_power()))
+        },
+        |(id, power)| {
+            id_sum += id.unwrap_or_default() as u64;
+            lowest_power_sum += power as u64;
+            Ok(())
+        },
+    )?;
+    Ok((id_sum, lowest_power_sum))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Model = Vec<Game>;
+    type Answer = u64;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        normalize(input)
+            .lines()
+            .enumerate()
+            .map(|(i, line)| line.parse::<Game>().map_err(|e| e.with_line_number(i + 1)))
+            .collect()
+    }
+
+    fn part_1(model: &Self::Model) -> Result<u64, Error> {
+        let possible = model.iter().filter(|game| game.is_possible(&BAG));
+        Ok(possible.map(|game| game.id as u64).sum())
+    }
+
+    fn part_2(model: &Self::Model) -> Result<u64, Error> {
+        Ok(model.iter().map(|game| game.get_power() as u64).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{solve, CubeSet, Day2, Error, Game};
+
+    use color_eyre::eyre::Result;
+    use common::{differential, input::windows, Solution};
+    use proptest::{collection::vec, prelude::*};
+
+    common::examples_test!(Day2);
+
+    fn cube_set() -> impl Strategy<Value = CubeSet> {
+        (any::<u8>(), any::<u8>(), any::<u8>()).prop_map(|(red, green, blue)| CubeSet {
+            red,
+            green,
+            blue,
+        })
+    }
+
+    // Reads the cubes of each game straight from the text, keeping the most of each color seen
+    fn reference(input: &str) -> Result<(String, String), String> {
+        let (mut part_1, mut part_2) = (0, 0);
+        for line in input.lines() {
+            let (game, sets) = line.split_once(": ").ok_or("game has no sets")?;
+            let id: u64 = game["Game ".len()..].parse().map_err(|e| format!("{e}"))?;
+            // Red, green and blue
+            let mut most = [0; 3];
+            for cubes in sets.split([',', ';']) {
+                let (count, color) = cubes.trim().split_once(' ').ok_or("cubes have no color")?;
+                let count: u64 = count.parse().map_err(|e| format!("{e}"))?;
+                let color = ["red", "green", "blue"]
+                    .iter()
+                    .position(|name| *name == color)
+                    .ok_or("cubes have an unknown color")?;
+                most[color] = most[color].max(count);
+            }
+            if most[0] <= 12 && most[1] <= 13 && most[2] <= 14 {
+                part_1 += id;
+            }
+            part_2 += most.iter().product::<u64>();
+        }
+        Ok((part_1.to_string(), part_2.to_string()))
+    }
+
+    #[test]
+    fn differential_test() -> Result<()> {
+        differential::compare::<Day2, _>(0..200, 40, reference, differential::solve::<Day2>)?;
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trip_test(id: u8, sets in vec(cube_set(), 1..6)) {
+            let game = Game { id, sets };
+            prop_assert_eq!(&game, &game.to_string().parse::<Game>()?);
+            for set in &game.sets {
+                prop_assert_eq!(set, &set.to_string().parse::<CubeSet>()?);
+            }
+        }
+    }
+
+    #[test]
+    fn calculate_part_1_test() -> Result<()> {
+        let input = include_str!("test.txt");
+        assert_eq!(8, solve(input.as_bytes())?.0);
+        Ok(())
+    }
+
+    #[test]
+    fn calculate_part_2_test() -> Result<()> {
+        let input = include_str!("test.txt");
+        assert_eq!(2286, solve(input.as_bytes())?.1);
+        Ok(())
+    }
+
+    #[test]
+    fn solution_test() -> Result<()> {
+        let model = Day2::parse(include_str!("test.txt"))?;
+        assert_eq!((8, 2286), (Day2::part_1(&model)?, Day2::part_2(&model)?));
+        Ok(())
+    }
+
+    #[test]
+    fn windows_input_test() -> Result<()> {
+        let input = windows(include_str!("test.txt"));
+        assert_eq!((8, 2286), solve(input.as_bytes())?);
+        let model = Day2::parse(&input)?;
+        assert_eq!((8, 2286), (Day2::part_1(&model)?, Day2::part_2(&model)?));
+        Ok(())
+    }
+
+    #[test]
+    fn diagnostic_test() {
+        let error = solve("Game 1: 3 blue, 4 red\nGame 2: 1 red; 2 purple\n".as_bytes());
+        let Err(Error::UnknownColor(error)) = error else {
+            panic!("expected an unknown color, got {error:?}");
+        };
+        assert_eq!((2, 17..23), (error.line_number, error.span));
+        assert_eq!("color_name was not a valid value", error.message);
+        let error = solve("Game 1: 300 blue".as_bytes());
+        let Err(Error::InvalidNumber(error)) = error else {
+            panic!("expected an invalid number, got {error:?}");
+        };
+        assert_eq!((1, 8..11), (error.line_number, error.span));
+        assert_eq!("number too large to fit in target type", error.label);
+        // Repeated colors used to overflow a u8 when added up
+        let error = solve("Game 1: 200 red, 100 red".as_bytes());
+        let Err(Error::TooManyCubes(error)) = error else {
+            panic!("expected too many cubes, got {error:?}");
+        };
+        assert_eq!((1, 17..24), (error.line_number, error.span));
+        let error = solve("Game 1 3 blue".as_bytes());
+        assert!(matches!(error, Err(Error::Malformed(_))), "{error:?}");
+    }
+}
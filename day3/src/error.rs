@@ -0,0 +1,56 @@
+use std::{
+    error::Error as StdError,
+    fmt::{self, Display, Formatter},
+};
+
+use common::Diagnostic;
+
+/// Why an engine schematic could not be solved
+#[derive(Debug)]
+pub enum Error {
+    /// The schematic is not a rectangular grid of characters
+    Malformed(Diagnostic),
+    /// A part number is too large
+    InvalidNumber(Diagnostic),
+    /// A gear ratio, or the sum of them all, is more than a `u64` holds
+    GearRatioOverflow,
+}
+
+impl Error {
+    /// Where in the input the error is, for errors that point at a line
+    pub fn diagnostic(&self) -> Option<&Diagnostic> {
+        match self {
+            Error::Malformed(diagnostic) | Error::InvalidNumber(diagnostic) => Some(diagnostic),
+            Error::GearRatioOverflow => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Malformed(diagnostic) | Error::InvalidNumber(diagnostic) => {
+                write!(f, "{diagnostic}")
+            }
+            Error::GearRatioOverflow => write!(f, "gear ratios do not fit in a u64"),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        // The diagnostic is what gets displayed, so its source comes next
+        self.diagnostic().and_then(StdError::source)
+    }
+}
+
+// Errors found by the parsers are about numbers when a number failed to parse, and otherwise
+// about the layout of the grid
+impl From<Diagnostic> for Error {
+    fn from(diagnostic: Diagnostic) -> Self {
+        match diagnostic.source {
+            Some(_) => Error::InvalidNumber(diagnostic),
+            None => Error::Malformed(diagnostic),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use common::generate::{Generator, Rng};
+
+use crate::Day3;
+
+const SYMBOLS: [char; 10] = ['*', '*', '*', '#', '+', '$', '/', '@', '=', '%'];
+
+// Generates a square schematic `size` characters wide. Numbers have up to three digits and are
+// always followed by a dot or a symbol, so neighbouring numbers never run together.
+impl Generator for Day3 {
+    fn generate(size: usize, seed: u64) -> String {
+        let size = size.max(1);
+        let mut rng = Rng::new(seed);
+        let mut rows = Vec::new();
+        for _ in 0..size {
+            let mut row = String::new();
+            let mut after_number = false;
+            while row.len() < size {
+                let number = rng.range(1..1000).to_string();
+                match rng.below(10) {
+                    0..=1 if !after_number && row.len() + number.len() <= size => {
+                        row.push_str(&number);
+                        after_number = true;
+                        continue;
+                    }
+                    2 => row.push(*rng.pick(&SYMBOLS)),
+                    _ => row.push('.'),
+                }
+                after_number = false;
+            }
+            rows.push(row.into_bytes());
+        }
+        // Every number next to a star is a factor of its gear ratio, so stars touching more
+        // than two numbers become another symbol to keep the ratios within a u32
+        for row in 0..rows.len() {
+            for column in 0..rows[row].len() {
+                if rows[row][column] == b'*' && adjacent_numbers(&rows, row, column) > 2 {
+                    rows[row][column] = b'#';
+                }
+            }
+        }
+        rows.into_iter()
+            .map(|row| String::from_utf8(row).unwrap() + "\n")
+            .collect()
+    }
+}
+
+fn adjacent_numbers(rows: &[Vec<u8>], row: usize, column: usize) -> usize {
+    rows[row.saturating_sub(1)..(row + 2).min(rows.len())]
+        .iter()
+        .map(|cells| {
+            let columns = column.saturating_sub(1)..(column + 2).min(cells.len());
+            // Count where numbers start, or where one enters from outside the neighbourhood
+            columns
+                .clone()
+                .filter(|&i| {
+                    cells[i].is_ascii_digit()
+                        && (i == columns.start || !cells[i - 1].is_ascii_digit())
+                })
+                .count()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use common::{generate::Generator, Solution};
+
+    use crate::Day3;
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = Day3::generate(60, 1);
+        assert_eq!(input, Day3::generate(60, 1));
+        assert_ne!(input, Day3::generate(60, 2));
+        let schematic = Day3::parse(&input)?;
+        assert_eq!(60, schematic.grid.len());
+        assert!(schematic.grid.iter().all(|row| row.len() == 60));
+        assert!(Day3::part_1(&schematic)? > 0);
+        assert!(Day3::part_2(&schematic)? > 0);
+        Ok(())
+    }
+}
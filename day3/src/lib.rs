@@ -0,0 +1,357 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    num::ParseIntError,
+    ops::Range,
+    str::FromStr,
+};
+
+use common::{
+    input::normalize,
+    parse::{grid, parse_all},
+    Diagnostic, Solution,
+};
+use nom::{character::complete::multispace0, sequence::terminated};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod error;
+mod generate;
+
+pub use error::Error;
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Part {
+    /// Row and column of the first digit
+    pub start: (usize, usize),
+    pub length: usize,
+    pub number: u32,
+}
+
+impl Part {
+    // Note that this grid will be outside of positive bounds of the grid
+    pub fn get_coord_grid(&self) -> Vec<(usize, usize)> {
+        let (x, y) = self.start;
+        let mut coords = Vec::new();
+        let range_x = x.saturating_sub(1)..=(x + 1);
+        for x in range_x {
+            let range_y = y.saturating_sub(1)..=(y + self.length);
+            for y in range_y {
+                coords.push((x, y));
+            }
+        }
+        coords
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Schematic {
+    pub grid: Vec<Vec<char>>,
+    pub parts: Vec<Part>,
+    /// Row and column of every `*`, whether or not it ends up being a gear
+    pub gears: Vec<(usize, usize)>,
+}
+
+impl Schematic {
+    pub fn get_valid_parts(&self) -> Vec<&Part> {
+        self.parts
+            .iter()
+            .filter(|part| {
+                // The rows to search are the ones above, of and below the part, where they
+                // exist. Taking them by iterator never runs past the edges of the grid.
+                let (x, y) = part.start;
+                let rows = self
+                    .grid
+                    .iter()
+                    .skip(x.saturating_sub(1))
+                    .take(x.min(1) + 2);
+                // then we iterate through the sub sections of each row around the number collecting all characters
+                rows.flat_map(|row| {
+                    row.iter()
+                        .skip(y.saturating_sub(1))
+                        .take(y.min(1) + part.length + 1)
+                })
+                // Finally we check if any of the characters are valid punctuation
+                .any(|char| char.is_ascii_punctuation() && *char != '.')
+            })
+            .collect()
+    }
+    /// The ratio of every gear, or `None` if one of them does not fit in a `u64`
+    pub fn get_gear_values(&self) -> Option<Vec<u64>> {
+        self.gears
+            .iter()
+            .filter_map(|gear| {
+                // Find all parts whose grid contains the gear
+                let adjacent_parts: Vec<u32> = self
+                    .parts
+                    .iter()
+                    .filter(|part| part.get_coord_grid().contains(gear))
+                    .map(|part| part.number)
+                    .collect();
+                // Only include gears that have more than one adjacent part
+                if adjacent_parts.len() > 1 {
+                    Some(
+                        adjacent_parts
+                            .iter()
+                            .try_fold(1u64, |product, number| product.checked_mul(*number as u64)),
+                    )
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Schematic {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = parse_all(s, terminated(grid, multispace0))?;
+        // Create a 2 dimensional grid of characters to represent the schematic grid
+        let grid: Vec<Vec<char>> = rows.iter().map(|row| row.chars().collect()).collect();
+        let mut parts = Vec::new();
+        let mut gears = Vec::new();
+        // Parse the digits of a number out of the input line so errors can point at them
+        let parse_part = |line: &str, start, length, bytes: Range<usize>| {
+            let token = &line[bytes];
+            let digits: String = token.chars().filter(|c| c.is_ascii_digit()).collect();
+            let number = digits.parse().map_err(|e: ParseIntError| {
+                Diagnostic::new(s, token, "part number is not valid", e.to_string()).with_source(e)
+            })?;
+            Ok::<_, Error>(Part {
+                start,
+                length,
+                number,
+            })
+        };
+        // Loop twice to get x,y coordinates for each character in the grid
+        for (x, line) in rows.into_iter().enumerate() {
+            let mut start = (0, 0);
+            let mut length = 0;
+            // Byte offset in the line where the number being tracked starts
+            let mut number_start = None;
+            for (y, (byte, char)) in line.char_indices().enumerate() {
+                // Using the rules given parse out part numbers regardless of validity
+                match char {
+                    d if d.is_ascii_digit() && number_start.is_none() => {
+                        start = (x, y);
+                        length = 1;
+                        number_start = Some(byte);
+                    }
+                    d if d.is_ascii_digit() => {
+                        length += 1;
+                    }
+                    d if d.is_ascii_punctuation() => {
+                        // Save out number if partial is being tracked
+                        if let Some(number_start) = number_start.take() {
+                            parts.push(parse_part(line, start, length, number_start..byte)?);
+                        }
+                        if d == '*' {
+                            gears.push((x, y));
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            // Handle numbers at the end of a line
+            if let Some(number_start) = number_start {
+                parts.push(parse_part(line, start, length, number_start..line.len())?);
+            }
+        }
+        Ok(Schematic { grid, parts, gears })
+    }
+}
+
+// Parts and gears are read from the grid, so writing the grid is enough to recreate them
+impl Display for Schematic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.grid.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Model = Schematic;
+    type Answer = u64;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Schematic, Error> {
+        normalize(input).parse()
+    }
+
+    fn part_1(schematic: &Schematic) -> Result<u64, Error> {
+        let valid_parts = schematic.get_valid_parts();
+        Ok(valid_parts.iter().map(|part| part.number as u64).sum())
+    }
+
+    fn part_2(schematic: &Schematic) -> Result<u64, Error> {
+        schematic
+            .get_gear_values()
+            .and_then(|gear_score| {
+                gear_score
+                    .iter()
+                    .try_fold(0u64, |sum, ratio| sum.checked_add(*ratio))
+            })
+            .ok_or(Error::GearRatioOverflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use proptest::{collection::vec, prelude::*, sample::select};
+
+    use common::{differential, input::windows, Solution};
+
+    use crate::{Day3, Error, Schematic};
+
+    common::examples_test!(Day3);
+
+    // Rows of a grid up to 9 wide, so that every number fits in a u32
+    fn rows() -> impl Strategy<Value = Vec<String>> {
+        let cell = select(vec!['.', '.', '.', '*', '#', '$', '+', '4', '6', '7']);
+        (1..10usize).prop_flat_map(move |width| {
+            vec(
+                vec(cell.clone(), width).prop_map(|row| row.into_iter().collect()),
+                1..10,
+            )
+        })
+    }
+    #[test]
+    fn calculate_part_1_test() -> Result<()> {
+        let schematic = Day3::parse(include_str!("test.txt"))?;
+        assert_eq!(4361, Day3::part_1(&schematic)?);
+        Ok(())
+    }
+    #[test]
+    fn calculate_part_2_test() -> Result<()> {
+        let schematic = Day3::parse(include_str!("test.txt"))?;
+        assert_eq!(467835, Day3::part_2(&schematic)?);
+        Ok(())
+    }
+
+    #[test]
+    fn windows_input_test() -> Result<()> {
+        let schematic = Day3::parse(&windows(include_str!("test.txt")))?;
+        assert_eq!(4361, Day3::part_1(&schematic)?);
+        assert_eq!(467835, Day3::part_2(&schematic)?);
+        Ok(())
+    }
+    #[test]
+    fn overflow_test() -> Result<()> {
+        // Gear ratios used to overflow a u32, and their sum still can overflow a u64
+        let schematic = Day3::parse("4294967295*4294967295\n")?;
+        assert_eq!(18446744065119617025, Day3::part_2(&schematic)?);
+        let gear = "4294967295*4294967295";
+        let input = format!("{gear}\n{}\n{gear}\n", ".".repeat(gear.len()));
+        let schematic = Day3::parse(&input)?;
+        assert!(matches!(
+            Day3::part_2(&schematic),
+            Err(Error::GearRatioOverflow)
+        ));
+        // Every part next to a gear is a factor of its ratio
+        let input = format!("{gear}\n{0}*{0}\n{0}4294967295.\n", ".".repeat(10));
+        assert!(matches!(
+            Day3::part_2(&Day3::parse(&input)?),
+            Err(Error::GearRatioOverflow)
+        ));
+        // A schematic built without a grid has no valid parts, rather than panicking
+        let schematic = Schematic {
+            grid: Vec::new(),
+            ..schematic
+        };
+        assert_eq!(0, Day3::part_1(&schematic)?);
+        Ok(())
+    }
+
+    #[test]
+    fn diagnostic_test() {
+        let input = "467..114.......\n...*...........\n..99999999999..\n";
+        let Err(Error::InvalidNumber(error)) = input.parse::<Schematic>() else {
+            panic!("expected the part number to be too large");
+        };
+        assert_eq!((3, 2..13), (error.line_number, error.span));
+        assert_eq!("number too large to fit in target type", error.label);
+        assert!(error.source.is_some());
+        let error = "...\n....\n".parse::<Schematic>();
+        assert!(matches!(error, Err(Error::Malformed(_))), "{error:?}");
+    }
+
+    // Looks around every number in the text itself, checking each neighbouring cell
+    fn reference(input: &str) -> Result<(String, String), String> {
+        let grid: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        // Row, first and last column and value of every number
+        let mut numbers = Vec::new();
+        for (x, row) in grid.iter().enumerate() {
+            let mut y = 0;
+            while y < row.len() {
+                let start = y;
+                while y < row.len() && row[y].is_ascii_digit() {
+                    y += 1;
+                }
+                if y > start {
+                    let digits = String::from_utf8_lossy(&row[start..y]);
+                    let value: u64 = digits.parse().map_err(|e| format!("{e}"))?;
+                    numbers.push((x, start, y - 1, value));
+                } else {
+                    y += 1;
+                }
+            }
+        }
+        let adjacent = |(x, first, last, _): (usize, usize, usize, u64), cell: (usize, usize)| {
+            x.abs_diff(cell.0) <= 1 && first <= cell.1 + 1 && cell.1 <= last + 1
+        };
+        let cells = grid
+            .iter()
+            .enumerate()
+            .flat_map(|(x, row)| row.iter().enumerate().map(move |(y, c)| ((x, y), *c)));
+        let symbols: Vec<_> = cells
+            .filter(|(_, c)| c.is_ascii_punctuation() && *c != b'.')
+            .collect();
+        let part_1: u64 = numbers
+            .iter()
+            .filter(|number| symbols.iter().any(|(cell, _)| adjacent(**number, *cell)))
+            .map(|number| number.3)
+            .sum();
+        let part_2: u64 = symbols
+            .iter()
+            .filter(|(_, c)| *c == b'*')
+            .filter_map(|(cell, _)| {
+                let parts: Vec<u64> = numbers
+                    .iter()
+                    .filter(|number| adjacent(**number, *cell))
+                    .map(|number| number.3)
+                    .collect();
+                (parts.len() > 1).then(|| parts.iter().product::<u64>())
+            })
+            .sum();
+        Ok((part_1.to_string(), part_2.to_string()))
+    }
+
+    #[test]
+    fn differential_test() -> Result<()> {
+        differential::compare::<Day3, _>(0..200, 24, reference, differential::solve::<Day3>)?;
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trip_test(rows in rows()) {
+            let input = rows.join("\n");
+            let schematic: Schematic = input.parse()?;
+            prop_assert_eq!(&input, &schematic.to_string());
+            prop_assert_eq!(schematic, input.parse::<Schematic>()?);
+        }
+    }
+}
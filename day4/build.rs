@@ -0,0 +1,7 @@
+use std::{env, io, path::Path};
+
+// Writes a test for every file in `examples`, which the tests include with `examples_test!`
+fn main() -> io::Result<()> {
+    let out_dir = env::var_os("OUT_DIR").expect("cargo sets OUT_DIR for build scripts");
+    common::examples::write_tests("examples", Path::new(&out_dir).join("examples_test.rs"))
+}
@@ -0,0 +1,90 @@
+use std::{
+    error::Error as StdError,
+    fmt::{self, Display, Formatter},
+    io,
+};
+
+use common::Diagnostic;
+
+/// Why a pile of scratchcards could not be solved
+#[derive(Debug)]
+pub enum Error {
+    /// A line is not laid out like `Card 1: 41 48 | 83 86`
+    Malformed(Diagnostic),
+    /// A card id or number is not a number, or too large
+    InvalidNumber(Diagnostic),
+    /// The points won by a card do not fit in a `usize`
+    ScoreOverflow { card: u8 },
+    /// The copies won by a card do not fit in a `usize`
+    CopiesOverflow { card: u8 },
+    /// The card with this id is not where the pile needs it, because the cards skip an id, are
+    /// out of order or won copies of cards past the end of the pile
+    MissingCard { id: usize },
+    /// The pile could not be read
+    Io(io::Error),
+}
+
+impl Error {
+    /// Where in the input the error is, for errors that point at a line
+    pub fn diagnostic(&self) -> Option<&Diagnostic> {
+        match self {
+            Error::Malformed(diagnostic) | Error::InvalidNumber(diagnostic) => Some(diagnostic),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn with_line_number(self, line_number: usize) -> Self {
+        match self {
+            Error::Malformed(d) => Error::Malformed(d.with_line_number(line_number)),
+            Error::InvalidNumber(d) => Error::InvalidNumber(d.with_line_number(line_number)),
+            error => error,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Malformed(diagnostic) | Error::InvalidNumber(diagnostic) => {
+                write!(f, "{diagnostic}")
+            }
+            Error::ScoreOverflow { card } => {
+                write!(f, "score of card {card} does not fit in a usize")
+            }
+            Error::CopiesOverflow { card } => {
+                write!(f, "cards won by card {card} do not fit in a usize")
+            }
+            Error::MissingCard { id } => {
+                write!(f, "attempted to access a non-existant card id, value: {id}")
+            }
+            Error::Io(_) => write!(f, "could not read the scratchcards"),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            // The diagnostic is what gets displayed, so its source comes next
+            error => error.diagnostic().and_then(StdError::source),
+        }
+    }
+}
+
+// Errors found by the parsers are about numbers when a number failed to parse, and otherwise
+// about the layout of the line
+impl From<Diagnostic> for Error {
+    fn from(diagnostic: Diagnostic) -> Self {
+        match diagnostic.source {
+            Some(_) => Error::InvalidNumber(diagnostic),
+            None => Error::Malformed(diagnostic),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
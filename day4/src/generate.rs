@@ -0,0 +1,58 @@
+use common::generate::{Generator, Rng};
+
+use crate::{Day4, Scratchcard};
+
+// Cards have 10 numbers and 25 winning numbers like the puzzle's. Matches average below one
+// per card so that the copies won stay bounded, and never reach past the last card.
+impl Generator for Day4 {
+    fn generate(size: usize, seed: u64) -> String {
+        // Card ids are a single byte
+        let size = size.min(u8::MAX as usize);
+        let mut rng = Rng::new(seed);
+        let mut input = String::new();
+        for id in 1..=size {
+            let mut pool: Vec<u8> = (1..100).collect();
+            rng.shuffle(&mut pool);
+            let matches = match rng.below(20) {
+                0..=11 => 0,
+                12..=16 => 1,
+                17..=18 => 2,
+                _ => rng.below(8) + 3,
+            }
+            .min(size - id);
+            let winning_numbers = pool[..25].to_vec();
+            let mut numbers = [&pool[..matches], &pool[25..35 - matches]].concat();
+            rng.shuffle(&mut numbers);
+            let card = Scratchcard {
+                id: id as u8,
+                numbers,
+                winning_numbers,
+            };
+            input.push_str(&card.to_string());
+            input.push('\n');
+        }
+        input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use common::{generate::Generator, Solution};
+
+    use crate::{solve, Day4};
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = Day4::generate(200, 1);
+        assert_eq!(input, Day4::generate(200, 1));
+        assert_ne!(input, Day4::generate(200, 2));
+        let model = Day4::parse(&input)?;
+        assert_eq!(200, model.len());
+        assert_eq!(255, Day4::parse(&Day4::generate(500, 1))?.len());
+        let answers = (Day4::part_1(&model)?, Day4::part_2(&model)?);
+        assert!(answers.1 > 200);
+        assert_eq!(answers, solve(input.as_bytes())?);
+        Ok(())
+    }
+}
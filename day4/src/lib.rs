@@ -0,0 +1,359 @@
+use std::{
+    collections::VecDeque,
+    fmt::{self, Display, Formatter},
+    io::BufRead,
+    str::FromStr,
+};
+
+use common::{
+    input::normalize,
+    parse::{integers, labeled_record, parse_all},
+    stream::map_lines,
+    Solution,
+};
+use nom::{character::complete::char, error::context, sequence::separated_pair};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod error;
+mod generate;
+
+pub use error::Error;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Scratchcard {
+    pub id: u8,
+    pub numbers: Vec<u8>,
+    pub winning_numbers: Vec<u8>,
+}
+
+impl Scratchcard {
+    pub fn num_matches(&self) -> usize {
+        self.numbers
+            .iter()
+            .filter(|num| self.winning_numbers.contains(num))
+            .count()
+    }
+}
+
+impl FromStr for Scratchcard {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Numbers and winning numbers are split on the "|" symbol
+        let numbers = separated_pair(
+            context("scratchcard number is not valid", integers),
+            context("input string did not have pipe", char('|')),
+            context("scratchcard number is not valid", integers),
+        );
+        let (id, (numbers, winning_numbers)) = parse_all(s, labeled_record("Card", numbers))?;
+        Ok(Scratchcard {
+            id,
+            numbers,
+            winning_numbers,
+        })
+    }
+}
+
+// Numbers are right aligned in columns two wide, the way the puzzle input is laid out
+impl Display for Scratchcard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Card {}:", self.id)?;
+        for number in &self.numbers {
+            write!(f, " {number:>2}")?;
+        }
+        write!(f, " |")?;
+        for number in &self.winning_numbers {
+            write!(f, " {number:>2}")?;
+        }
+        Ok(())
+    }
+}
+
+// Tallies the points and copies won as cards are scratched in order
+#[derive(Default)]
+struct Tally {
+    score: usize,
+    total_cards: usize,
+    // Extra copies won of the upcoming cards, front first. This never grows longer than the
+    // most matches on a single card, which keeps memory use constant
+    won_copies: VecDeque<usize>,
+    last_id: usize,
+}
+
+impl Tally {
+    fn scratch(&mut self, id: u8, matches: usize) -> Result<(), Error> {
+        // Copies are won by position, so every card has to follow the one before it
+        if id as usize != self.last_id + 1 {
+            return Err(Error::MissingCard {
+                id: self.last_id + 1,
+            });
+        }
+        self.score = points(matches)
+            .and_then(|points| self.score.checked_add(points))
+            .ok_or(Error::ScoreOverflow { card: id })?;
+        let overflow = || Error::CopiesOverflow { card: id };
+        let won = self.won_copies.pop_front().unwrap_or_default();
+        let copies = won.checked_add(1).ok_or_else(overflow)?;
+        self.total_cards = self.total_cards.checked_add(copies).ok_or_else(overflow)?;
+        if self.won_copies.len() < matches {
+            self.won_copies.resize(matches, 0);
+        }
+        for count in self.won_copies.iter_mut().take(matches) {
+            // Copies won are always part of the total as well, so this cannot overflow if the
+            // total did not
+            *count += copies;
+        }
+        self.last_id = id as usize;
+        Ok(())
+    }
+
+    // Returns the score and the number of cards ended up with
+    fn finish(self) -> Result<(usize, usize), Error> {
+        // Copies left over were won for cards that do not exist
+        if !self.won_copies.is_empty() {
+            return Err(Error::MissingCard {
+                id: self.last_id + 1,
+            });
+        }
+        Ok((self.score, self.total_cards))
+    }
+}
+
+// The first match is worth one point and every further match doubles it. Returns None when
+// the points do not fit in a usize, which takes a card with duplicate numbers.
+fn points(matches: usize) -> Option<usize> {
+    match matches {
+        0 => Some(0),
+        matches => 1usize.checked_shl(u32::try_from(matches - 1).ok()?),
+    }
+}
+
+/// Solves both parts in a single pass over the input so that it can be streamed from a pipe
+pub fn solve(reader: impl BufRead) -> Result<(usize, usize), Error> {
+    let mut tally = Tally::default();
+    // Cards are matched independently, only the copies won depend on the order of the cards
+    let matched = |number, line: &str| {
+        let card: Scratchcard = line
+            .parse()
+            .map_err(|e: Error| e.with_line_number(number))?;
+        Ok((card.id, card.num_matches()))
+    };
+    map_lines(reader, matched, |(id, matches)| tally.scratch(id, matches))?;
+    tally.finish()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Model = Vec<Scratchcard>;
+    type Answer = usize;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        normalize(input)
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                line.parse::<Scratchcard>()
+                    .map_err(|e| e.with_line_number(i + 1))
+            })
+            .collect()
+    }
+
+    fn part_1(model: &Self::Model) -> Result<usize, Error> {
+        model.iter().try_fold(0usize, |score, card| {
+            points(card.num_matches())
+                .and_then(|points| score.checked_add(points))
+                .ok_or(Error::ScoreOverflow { card: card.id })
+        })
+    }
+
+    fn part_2(model: &Self::Model) -> Result<usize, Error> {
+        let mut tally = Tally::default();
+        for card in model {
+            tally.scratch(card.id, card.num_matches())?;
+        }
+        Ok(tally.finish()?.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use common::{differential, generate::Generator, input::windows, Solution};
+    use proptest::{collection::vec, prelude::*};
+
+    use crate::{solve, Day4, Error, Scratchcard};
+
+    common::examples_test!(Day4);
+
+    #[test]
+    fn calculate_part_1_test() -> Result<()> {
+        let input = include_str!("test.txt");
+        assert_eq!(13, solve(input.as_bytes())?.0);
+        Ok(())
+    }
+
+    #[test]
+    fn calculate_part_2_test() -> Result<()> {
+        let input = include_str!("test.txt");
+        assert_eq!(30, solve(input.as_bytes())?.1);
+        Ok(())
+    }
+
+    #[test]
+    fn solution_test() -> Result<()> {
+        let model = Day4::parse(include_str!("test.txt"))?;
+        assert_eq!((13, 30), (Day4::part_1(&model)?, Day4::part_2(&model)?));
+        Ok(())
+    }
+
+    #[test]
+    fn windows_input_test() -> Result<()> {
+        let input = windows(include_str!("test.txt"));
+        assert_eq!((13, 30), solve(input.as_bytes())?);
+        let model = Day4::parse(&input)?;
+        assert_eq!((13, 30), (Day4::part_1(&model)?, Day4::part_2(&model)?));
+        Ok(())
+    }
+
+    #[test]
+    fn diagnostic_test() {
+        let error = solve("Card 1: 41 48 | 83 86\nCard 2: 13 32 20 16 61\n".as_bytes());
+        let Err(Error::Malformed(error)) = error else {
+            panic!("expected a missing pipe, got {error:?}");
+        };
+        assert_eq!((2, 22..22), (error.line_number, error.span));
+        assert_eq!("input string did not have pipe", error.message);
+        let error = solve("Card 1: 41 4x8 | 83 86".as_bytes());
+        let Err(Error::InvalidNumber(error)) = error else {
+            panic!("expected an invalid number, got {error:?}");
+        };
+        assert_eq!((1, 11..14), (error.line_number, error.span));
+    }
+
+    #[test]
+    fn overflow_test() {
+        // Duplicate numbers used to overflow the score, and long chains of copies the count
+        let card = format!("Card 1: {}| 1", "1 ".repeat(65));
+        let error = solve(card.as_bytes()).unwrap_err();
+        assert!(matches!(error, Error::ScoreOverflow { card: 1 }));
+        assert_eq!("score of card 1 does not fit in a usize", error.to_string());
+        let cards: String = (1..=100)
+            .map(|id| format!("Card {id}: 1 2 | 1 2\n"))
+            .collect();
+        let error = solve(cards.as_bytes()).unwrap_err();
+        assert!(matches!(error, Error::CopiesOverflow { .. }), "{error:?}");
+    }
+
+    #[test]
+    fn missing_card_test() {
+        let error = solve("Card 1: 41 48 | 48 86\n".as_bytes()).unwrap_err();
+        assert!(matches!(error, Error::MissingCard { id: 2 }));
+        assert_eq!(
+            "attempted to access a non-existant card id, value: 2",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn card_order_test() -> Result<()> {
+        // Cards have to be numbered from 1 without gaps
+        for (input, id) in [
+            ("Card 1: 1 | 1\nCard 3: 2 | 3\n", 2),
+            ("Card 2: 1 | 2\nCard 1: 3 | 4\n", 1),
+        ] {
+            let message = format!("attempted to access a non-existant card id, value: {id}");
+            let error = solve(input.as_bytes()).unwrap_err();
+            assert_eq!(message, error.to_string());
+            let error = Day4::part_2(&Day4::parse(input)?).unwrap_err();
+            assert_eq!(message, error.to_string());
+        }
+        Ok(())
+    }
+
+    // Counts matches straight from the text and hands out copies by card id, refusing piles
+    // whose ids skip or repeat, or whose cards win copies of cards that are not there
+    fn reference(input: &str) -> Result<(String, String), String> {
+        let mut cards = Vec::new();
+        for line in input.lines() {
+            let (card, numbers) = line.split_once(':').ok_or("card has no numbers")?;
+            let id: usize = card["Card".len()..]
+                .trim()
+                .parse()
+                .map_err(|e| format!("{e}"))?;
+            let (numbers, winning) = numbers.split_once('|').ok_or("card has no winners")?;
+            let winning: Vec<&str> = winning.split_whitespace().collect();
+            let matches = numbers
+                .split_whitespace()
+                .filter(|number| winning.contains(number))
+                .count();
+            cards.push((id, matches));
+        }
+        let score: usize = cards
+            .iter()
+            .filter(|(_, matches)| *matches > 0)
+            .map(|(_, matches)| 1 << (matches - 1))
+            .sum();
+        if let Some(i) = (0..cards.len()).find(|&i| cards[i].0 != i + 1) {
+            return Err(Error::MissingCard { id: i + 1 }.to_string());
+        }
+        let mut copies = vec![1; cards.len() + 1];
+        for (id, matches) in cards {
+            for won in id + 1..=id + matches {
+                let Some(count) = copies.get(won).copied() else {
+                    return Err(Error::MissingCard { id: won }.to_string());
+                };
+                copies[won] = count + copies[id];
+            }
+        }
+        Ok((
+            score.to_string(),
+            copies[1..].iter().sum::<usize>().to_string(),
+        ))
+    }
+
+    // Generated piles with one card left out, which leaves a gap in the ids or, when it is the
+    // last card, can leave copies won of a card that is not there
+    struct MissingCard;
+
+    impl Generator for MissingCard {
+        fn generate(size: usize, seed: u64) -> String {
+            let input = Day4::generate(size, seed);
+            let missing = seed as usize % size.max(1);
+            let lines = input.lines().enumerate();
+            lines
+                .filter(|(i, _)| *i != missing)
+                .map(|(_, line)| format!("{line}\n"))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn differential_test() -> Result<()> {
+        differential::compare::<Day4, _>(0..200, 40, reference, differential::solve::<Day4>)?;
+        differential::compare::<MissingCard, _>(
+            0..200,
+            40,
+            reference,
+            differential::solve::<Day4>,
+        )?;
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trip_test(
+            id: u8,
+            numbers in vec(any::<u8>(), 0..10),
+            winning_numbers in vec(any::<u8>(), 0..10),
+        ) {
+            let card = Scratchcard { id, numbers, winning_numbers };
+            prop_assert_eq!(&card, &card.to_string().parse::<Scratchcard>()?);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use common::{generate::Generator, Solution};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use day5::{Day5, RangeMap};
+
+// The previous lookup strategy, kept here as a baseline for comparison
+fn map_source_linear(map: &RangeMap, source: u64) -> u64 {
+    map.pairs()
+        .iter()
+        .find(|pair| (pair.source.0..pair.source.0 + pair.source.1).contains(&source))
+        .map_or(source, |pair| pair.destination.0 + (source - pair.source.0))
+}
+
+fn lowest_seed(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lowest_seed");
+    for ranges in [100, 1000, 5000] {
+        let almanac = Day5::parse(&Day5::generate(ranges, 1)).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("binary_search", ranges),
+            &almanac,
+            |b, almanac| b.iter(|| black_box(almanac).get_lowest_seed()),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("linear_scan", ranges),
+            &almanac,
+            |b, almanac| {
+                b.iter(|| {
+                    let almanac = black_box(almanac);
+                    almanac
+                        .seeds
+                        .iter()
+                        .map(|seed| {
+                            almanac
+                                .maps
+                                .iter()
+                                .fold(*seed, |acc, map| map_source_linear(map, acc))
+                        })
+                        .min()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, lowest_seed);
+criterion_main!(benches);
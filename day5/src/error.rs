@@ -0,0 +1,65 @@
+use std::{
+    error::Error as StdError,
+    fmt::{self, Display, Formatter},
+};
+
+use common::Diagnostic;
+
+/// Why an almanac could not be solved
+#[derive(Debug)]
+pub enum Error {
+    /// The seeds or a map section are not laid out the way the almanac writes them
+    Malformed(Diagnostic),
+    /// A seed or range value is not a number, or too large
+    InvalidNumber(Diagnostic),
+    /// A range rejected by strict validation, see [`crate::RangeWarning`] for why
+    InvalidRange(Diagnostic),
+    /// There are no seeds to find the lowest location of
+    NoSeeds,
+    /// A seed range whose end does not fit in a `u64`
+    SeedRangeOverflow { start: u64 },
+}
+
+impl Error {
+    /// Where in the input the error is, for errors that point at a line
+    pub fn diagnostic(&self) -> Option<&Diagnostic> {
+        match self {
+            Error::Malformed(diagnostic)
+            | Error::InvalidNumber(diagnostic)
+            | Error::InvalidRange(diagnostic) => Some(diagnostic),
+            Error::NoSeeds | Error::SeedRangeOverflow { .. } => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Malformed(diagnostic)
+            | Error::InvalidNumber(diagnostic)
+            | Error::InvalidRange(diagnostic) => write!(f, "{diagnostic}"),
+            Error::NoSeeds => write!(f, "the almanac lists no seeds"),
+            Error::SeedRangeOverflow { start } => {
+                write!(f, "seed range starting at {start} overflows a u64")
+            }
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        // The diagnostic is what gets displayed, so its source comes next
+        self.diagnostic().and_then(StdError::source)
+    }
+}
+
+// Errors found by the parsers are about numbers when a number failed to parse, and otherwise
+// about the layout of the almanac
+impl From<Diagnostic> for Error {
+    fn from(diagnostic: Diagnostic) -> Self {
+        match diagnostic.source {
+            Some(_) => Error::InvalidNumber(diagnostic),
+            None => Error::Malformed(diagnostic),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use common::generate::{Generator, Rng};
+
+use crate::{Almanac, Day5, RangeMap, RangePair};
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+// Generates the puzzle's seven maps with `size` ranges each, listed in shuffled order, and
+// `size` seed ranges. Seed ranges are at most a thousand long so part 2 stays quick to solve.
+impl Generator for Day5 {
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        let mut end = 0;
+        let maps: Vec<RangeMap> = CATEGORIES
+            .windows(2)
+            .map(|pair| {
+                end = 0;
+                let mut maps: Vec<RangePair> = (0..size)
+                    .map(|_| {
+                        let start = end + rng.range(0..1000);
+                        let length = rng.range(1..1000);
+                        end = start + length;
+                        RangePair {
+                            source: (start, length),
+                            destination: (rng.range(0..size as u64 * 1000), length),
+                        }
+                    })
+                    .collect();
+                rng.shuffle(&mut maps);
+                RangeMap {
+                    name: format!("{}-to-{}", pair[0], pair[1]),
+                    maps,
+                }
+            })
+            .collect();
+        let seeds = (0..size)
+            .flat_map(|_| [rng.range(0..end.max(1)), rng.range(1..1000)])
+            .collect();
+        // The maps are written in their shuffled order, parsing sorts them again
+        let almanac = Almanac { seeds, maps };
+        format!("{almanac}\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use common::{generate::Generator, Solution};
+
+    use crate::Day5;
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = Day5::generate(200, 1);
+        assert_eq!(input, Day5::generate(200, 1));
+        assert_ne!(input, Day5::generate(200, 2));
+        let almanac = Day5::parse(&input)?;
+        assert_eq!((400, 7), (almanac.seeds.len(), almanac.maps.len()));
+        assert!(almanac.maps.iter().all(|map| map.pairs().len() == 200));
+        Day5::part_1(&almanac)?;
+        // The start of every seed range is one of the seeds tried in part 2
+        let starts = almanac
+            .seeds
+            .chunks(2)
+            .map(|pair| almanac.location(pair[0]));
+        assert!(Day5::part_2(&almanac)? <= starts.min().unwrap());
+        Ok(())
+    }
+}
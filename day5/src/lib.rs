@@ -0,0 +1,599 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use common::{
+    input::normalize,
+    line_number,
+    parse::{self, blank_line, integers, parse_all, sections, token, word, IResult},
+    Diagnostic, Solution,
+};
+#[cfg(not(feature = "parallel"))]
+use itertools::Itertools;
+use nom::{
+    character::complete::{line_ending, multispace0, satisfy},
+    combinator::{consumed, cut, opt, peek},
+    error::context,
+    multi::many0,
+    sequence::{pair, preceded, terminated},
+};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod error;
+mod generate;
+
+pub use error::Error;
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RangePair {
+    pub source: (u64, u64),
+    pub destination: (u64, u64),
+}
+
+/// Controls whether problems found while validating range maps abort parsing
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Validation {
+    #[default]
+    Strict,
+    /// Offending ranges are skipped and reported as warnings instead
+    Lenient,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RangeIssue {
+    ZeroLength,
+    Overflow,
+    Overlap { previous_line: usize },
+}
+
+/// A problem with a single range line of a map, located by map name and 1-based line number
+#[derive(Debug, PartialEq, Eq)]
+pub struct RangeWarning {
+    pub map: String,
+    pub line: usize,
+    pub issue: RangeIssue,
+}
+
+impl Display for RangeWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let RangeWarning { map, line, issue } = self;
+        match issue {
+            RangeIssue::ZeroLength => write!(f, "{map} map, line {line}: range has zero length"),
+            RangeIssue::Overflow => {
+                write!(f, "{map} map, line {line}: range end overflows a u64")
+            }
+            RangeIssue::Overlap { previous_line } => write!(
+                f,
+                "{map} map, line {line}: source range overlaps the range on line {previous_line}"
+            ),
+        }
+    }
+}
+
+/// A named map section whose pairs are kept sorted by source start and never overlap
+#[derive(Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RangeMapData"))]
+pub struct RangeMap {
+    pub name: String,
+    maps: Vec<RangePair>,
+}
+
+// Deserialized maps go through this so that their pairs are sorted and checked the same way
+// parsed ones are
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RangeMapData {
+    name: String,
+    maps: Vec<RangePair>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RangeMapData> for RangeMap {
+    type Error = String;
+
+    fn try_from(data: RangeMapData) -> Result<Self, String> {
+        let RangeMapData { name, mut maps } = data;
+        maps.sort_by_key(|pair| pair.source.0);
+        let mut end = None;
+        for pair in &maps {
+            let (start, length) = pair.source;
+            if length == 0 || pair.destination.1 != length {
+                return Err(format!(
+                    "{name} map: range at {start} has an invalid length"
+                ));
+            }
+            if end.is_some_and(|end| end > start) {
+                return Err(format!(
+                    "{name} map: range at {start} overlaps the one before it"
+                ));
+            }
+            end = start.checked_add(length);
+            if end.is_none() || pair.destination.0.checked_add(length).is_none() {
+                return Err(format!("{name} map: range at {start} overflows a u64"));
+            }
+        }
+        Ok(RangeMap { name, maps })
+    }
+}
+
+// A map section as written in the almanac, before its ranges are validated. Each row keeps
+// the slice of input it was parsed from so that problems can be reported against it.
+struct MapSection<'a> {
+    name: &'a str,
+    rows: Vec<(&'a str, (u64, u64, u64))>,
+}
+
+// Parses a line of destination, source and length values
+fn range_row(input: &str) -> IResult<'_, (&str, (u64, u64, u64))> {
+    let (rest, (row, values)) = consumed(integers)(input)?;
+    match values[..] {
+        [destination, start, length] => Ok((rest, (row, (destination, start, length)))),
+        _ => parse::Error::failure(
+            row,
+            "destination, source, length values not present in data",
+            "expected three numbers",
+        ),
+    }
+}
+
+// Parses a "<name> map:" header followed by its rows, which are optional
+fn map_section(input: &str) -> IResult<'_, MapSection<'_>> {
+    let header = context(
+        "map header was not properly formatted",
+        terminated(word, token(" map:")),
+    );
+    // Rows always start with a digit, anything else ends the section
+    let row = preceded(peek(satisfy(|c| c.is_ascii_digit())), cut(range_row));
+    let (rest, (name, rows)) = pair(header, many0(preceded(line_ending, row)))(input)?;
+    Ok((rest, MapSection { name, rows }))
+}
+
+impl RangeMap {
+    // Validates the rows of a section, which must be slices of source as errors and warnings are
+    // reported against it
+    fn from_section(
+        source: &str,
+        section: MapSection,
+        validation: Validation,
+        warnings: &mut Vec<RangeWarning>,
+    ) -> Result<Self, Error> {
+        let mut range_map = RangeMap {
+            name: section.name.to_string(),
+            ..Default::default()
+        };
+        // Accepted pairs sorted by source start, alongside their line numbers so that
+        // overlaps can be reported against the earlier line
+        let mut sorted: Vec<(RangePair, usize)> = Vec::new();
+        for (part, (destination, start, length)) in section.rows {
+            let line = line_number(source, part);
+            let pair = RangePair {
+                destination: (destination, length),
+                source: (start, length),
+            };
+            // Since accepted pairs are disjoint only the neighbours at the insertion
+            // point can overlap the new pair
+            let index = sorted.partition_point(|(other, _)| other.source.0 < start);
+            let issue = if length == 0 {
+                Some(RangeIssue::ZeroLength)
+            } else if start.checked_add(length).is_none()
+                || destination.checked_add(length).is_none()
+            {
+                Some(RangeIssue::Overflow)
+            } else {
+                let previous = index.checked_sub(1).map(|i| &sorted[i]);
+                let next = sorted.get(index);
+                previous
+                    .filter(|(other, _)| other.source.0 + other.source.1 > start)
+                    .or(next.filter(|(other, _)| other.source.0 < start + length))
+                    .map(|(_, previous_line)| RangeIssue::Overlap {
+                        previous_line: *previous_line,
+                    })
+            };
+            match issue {
+                Some(issue) => {
+                    let warning = RangeWarning {
+                        map: range_map.name.clone(),
+                        line,
+                        issue,
+                    };
+                    match validation {
+                        Validation::Strict => {
+                            let label = match warning.issue {
+                                RangeIssue::ZeroLength => "zero length range".to_string(),
+                                RangeIssue::Overflow => "range end overflows".to_string(),
+                                RangeIssue::Overlap { previous_line } => {
+                                    format!("overlaps line {previous_line}")
+                                }
+                            };
+                            let diagnostic =
+                                Diagnostic::new(source, part, warning.to_string(), label);
+                            return Err(Error::InvalidRange(diagnostic));
+                        }
+                        Validation::Lenient => warnings.push(warning),
+                    }
+                }
+                None => sorted.insert(index, (pair, line)),
+            }
+        }
+        range_map.maps = sorted.into_iter().map(|(pair, _)| pair).collect();
+        Ok(range_map)
+    }
+
+    pub fn pairs(&self) -> &[RangePair] {
+        &self.maps
+    }
+
+    pub fn map_source(&self, source: u64) -> u64 {
+        // Find the last pair starting at or before the source, it is the only candidate
+        let index = self.maps.partition_point(|pair| pair.source.0 <= source);
+        match index.checked_sub(1).map(|i| &self.maps[i]) {
+            Some(pair) if source - pair.source.0 < pair.source.1 => {
+                pair.destination.0 + (source - pair.source.0)
+            }
+            _ => source,
+        }
+    }
+}
+
+/// Parses a single map section with strict validation
+impl FromStr for RangeMap {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let section = parse_all(s, terminated(map_section, multispace0))?;
+        RangeMap::from_section(s, section, Validation::Strict, &mut Vec::new())
+    }
+}
+
+// Rows are written in source order, which may differ from the order they were read in
+impl Display for RangeMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} map:", self.name)?;
+        for pair in &self.maps {
+            let (destination, length) = pair.destination;
+            write!(f, "\n{destination} {} {length}", pair.source.0)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<RangeMap>,
+}
+
+impl Almanac {
+    // Follows a seed through every map to its location
+    fn location(&self, seed: u64) -> u64 {
+        self.maps.iter().fold(seed, |acc, map| map.map_source(acc))
+    }
+    #[cfg(not(feature = "parallel"))]
+    pub fn get_lowest_seed(&self) -> Option<u64> {
+        self.seeds.iter().map(|seed| self.location(*seed)).min()
+    }
+    #[cfg(feature = "parallel")]
+    pub fn get_lowest_seed(&self) -> Option<u64> {
+        self.seeds.par_iter().map(|seed| self.location(*seed)).min()
+    }
+    /// Seed ranges running past the largest u64 are cut off there
+    #[cfg(not(feature = "parallel"))]
+    pub fn get_lowest_seed_range(&self) -> Option<u64> {
+        self.seeds
+            .iter()
+            .tuples()
+            .flat_map(|(start, length)| {
+                (*start..start.saturating_add(*length)).map(|seed| self.location(seed))
+            })
+            .min()
+    }
+    /// Seed ranges running past the largest u64 are cut off there
+    #[cfg(feature = "parallel")]
+    pub fn get_lowest_seed_range(&self) -> Option<u64> {
+        self.seeds
+            .par_chunks_exact(2)
+            .flat_map(|pair| (pair[0]..pair[0].saturating_add(pair[1])).into_par_iter())
+            .map(|seed| self.location(seed))
+            .min()
+    }
+}
+
+impl Almanac {
+    pub fn parse(s: &str, validation: Validation) -> Result<(Self, Vec<RangeWarning>), Error> {
+        let seeds = preceded(token("seeds:"), integers);
+        let maps = opt(preceded(blank_line, sections(map_section)));
+        let (seeds, sections) = parse_all(s, terminated(pair(seeds, maps), multispace0))?;
+        let mut warnings = Vec::new();
+        let maps = sections
+            .unwrap_or_default()
+            .into_iter()
+            .map(|section| RangeMap::from_section(s, section, validation, &mut warnings))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((Almanac { seeds, maps }, warnings))
+    }
+}
+
+impl FromStr for Almanac {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (almanac, _) = Almanac::parse(s, Validation::Strict)?;
+        Ok(almanac)
+    }
+}
+
+impl Display for Almanac {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "seeds:")?;
+        for seed in &self.seeds {
+            write!(f, " {seed}")?;
+        }
+        for map in &self.maps {
+            write!(f, "\n\n{map}")?;
+        }
+        Ok(())
+    }
+}
+
+pub fn calculate_part_1(almanac: &Almanac) -> Result<u64, Error> {
+    almanac.get_lowest_seed().ok_or(Error::NoSeeds)
+}
+
+pub fn calculate_part_2(almanac: &Almanac) -> Result<u64, Error> {
+    let overflowing = almanac
+        .seeds
+        .chunks_exact(2)
+        .find(|pair| pair[0].checked_add(pair[1]).is_none());
+    if let Some(pair) = overflowing {
+        return Err(Error::SeedRangeOverflow { start: pair[0] });
+    }
+    almanac.get_lowest_seed_range().ok_or(Error::NoSeeds)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Model = Almanac;
+    type Answer = u64;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Almanac, Error> {
+        normalize(input).parse()
+    }
+
+    fn part_1(almanac: &Almanac) -> Result<u64, Error> {
+        calculate_part_1(almanac)
+    }
+
+    fn part_2(almanac: &Almanac) -> Result<u64, Error> {
+        calculate_part_2(almanac)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use common::{differential, input::windows, Solution};
+    use proptest::{collection::vec, prelude::*};
+
+    use crate::{
+        calculate_part_1, calculate_part_2, Almanac, Day5, Error, RangeIssue, RangeMap, RangePair,
+        RangeWarning, Validation,
+    };
+
+    common::examples_test!(Day5);
+
+    // Valid maps, built from the gap before and length of each source range
+    fn range_map() -> impl Strategy<Value = RangeMap> {
+        let pair = (0..1000u64, 1..1000u64, any::<u32>());
+        ("[a-z]{1,8}-to-[a-z]{1,8}", vec(pair, 0..8)).prop_map(|(name, pairs)| {
+            let mut start = 0;
+            let maps = pairs
+                .into_iter()
+                .map(|(gap, length, destination)| {
+                    start += gap;
+                    let pair = RangePair {
+                        source: (start, length),
+                        destination: (destination as u64, length),
+                    };
+                    start += length;
+                    pair
+                })
+                .collect();
+            RangeMap { name, maps }
+        })
+    }
+    #[test]
+    fn calculate_part_1_test() -> Result<()> {
+        let almanac: Almanac = include_str!("test.txt").parse()?;
+        assert_eq!(35, calculate_part_1(&almanac)?);
+        Ok(())
+    }
+    #[test]
+    fn calculate_part_2_test() -> Result<()> {
+        let almanac: Almanac = include_str!("test.txt").parse()?;
+        assert_eq!(46, calculate_part_2(&almanac)?);
+        Ok(())
+    }
+
+    #[test]
+    fn windows_input_test() -> Result<()> {
+        let almanac = Day5::parse(&windows(include_str!("test.txt")))?;
+        assert_eq!((35, 46), (Day5::part_1(&almanac)?, Day5::part_2(&almanac)?));
+        // A separator line holding only whitespace used to be read as a map row
+        let input = include_str!("test.txt").replacen("\n\n", "\n  \n", 2);
+        assert_eq!(almanac, Day5::parse(&input)?);
+        Ok(())
+    }
+    #[test]
+    fn map_source_test() -> Result<()> {
+        let almanac: Almanac =
+            "seeds: 1\n\nseed-to-soil map:\n52 50 48\n50 98 2\n0 10 5\n".parse()?;
+        let map = &almanac.maps[0];
+        let sources =
+            [10, 98, 50].map(|source| map.pairs().iter().position(|p| p.source.0 == source));
+        assert_eq!([Some(0), Some(2), Some(1)], sources);
+        for (source, destination) in [
+            (9, 9),
+            (10, 0),
+            (14, 4),
+            (15, 15),
+            (49, 49),
+            (50, 52),
+            (97, 99),
+            (98, 50),
+            (99, 51),
+            (100, 100),
+        ] {
+            assert_eq!(destination, map.map_source(source));
+        }
+        Ok(())
+    }
+    #[test]
+    fn seed_overflow_test() {
+        // The end of a seed range used to overflow
+        let almanac: Almanac = "seeds: 18446744073709551614 5\n".parse().unwrap();
+        let error = calculate_part_2(&almanac).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::SeedRangeOverflow {
+                start: 18446744073709551614
+            }
+        ));
+        assert_eq!(
+            "seed range starting at 18446744073709551614 overflows a u64",
+            error.to_string()
+        );
+        assert_eq!(Some(u64::MAX - 1), almanac.get_lowest_seed_range());
+    }
+    #[test]
+    fn no_seeds_test() -> Result<()> {
+        let almanac: Almanac = "seeds:\n".parse()?;
+        assert!(matches!(calculate_part_1(&almanac), Err(Error::NoSeeds)));
+        assert!(matches!(calculate_part_2(&almanac), Err(Error::NoSeeds)));
+        let Err(Error::InvalidNumber(_)) = "seeds: 1 99999999999999999999\n".parse::<Almanac>()
+        else {
+            panic!("expected an invalid number");
+        };
+        Ok(())
+    }
+    #[test]
+    fn validation_test() -> Result<()> {
+        let input =
+            "seeds: 1 2\n\nseed-to-soil map:\n50 98 2\n10 99 5\n0 5 0\n0 18446744073709551615 2\n";
+        let error = input.parse::<Almanac>();
+        let Err(Error::InvalidRange(error)) = error else {
+            panic!("expected an invalid range, got {error:?}");
+        };
+        assert_eq!(
+            "seed-to-soil map, line 5: source range overlaps the range on line 4",
+            error.message
+        );
+        assert_eq!(
+            (5, 0..7, "overlaps line 4"),
+            (error.line_number, error.span, error.label.as_str())
+        );
+        let (almanac, warnings) = Almanac::parse(input, Validation::Lenient)?;
+        assert_eq!(1, almanac.maps[0].maps.len());
+        let issues = [
+            (5, RangeIssue::Overlap { previous_line: 4 }),
+            (6, RangeIssue::ZeroLength),
+            (7, RangeIssue::Overflow),
+        ];
+        assert_eq!(issues.len(), warnings.len());
+        for (warning, (line, issue)) in warnings.into_iter().zip(issues) {
+            assert_eq!(
+                RangeWarning {
+                    map: "seed-to-soil".to_string(),
+                    line,
+                    issue
+                },
+                warning
+            );
+        }
+        Ok(())
+    }
+
+    // Reads the seeds and maps straight from the text and follows each seed through every line
+    // of every map, and part 2 one seed at a time
+    fn reference(input: &str) -> Result<(String, String), String> {
+        let numbers = |line: &str| {
+            line.split_whitespace()
+                .map(|number| number.parse::<u64>().map_err(|e| format!("{e}")))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let mut sections = input.split("\n\n");
+        let seeds = numbers(&sections.next().unwrap_or_default()["seeds:".len()..])?;
+        // Destination, source and length of every line of every map, in the order written
+        let maps = sections
+            .map(|section| section.lines().skip(1).map(numbers).collect())
+            .collect::<Result<Vec<Vec<_>>, _>>()?;
+        let location = |seed: u64| {
+            maps.iter().fold(seed, |value, lines| {
+                let line = lines
+                    .iter()
+                    .find(|line| (line[1]..line[1] + line[2]).contains(&value));
+                line.map_or(value, |line| line[0] + (value - line[1]))
+            })
+        };
+        let part_1 = seeds.iter().map(|seed| location(*seed)).min();
+        let part_2 = seeds
+            .chunks_exact(2)
+            .flat_map(|pair| pair[0]..pair[0] + pair[1])
+            .map(location)
+            .min();
+        match (part_1, part_2) {
+            (Some(part_1), Some(part_2)) => Ok((part_1.to_string(), part_2.to_string())),
+            _ => Err(Error::NoSeeds.to_string()),
+        }
+    }
+
+    #[test]
+    fn differential_test() -> Result<()> {
+        differential::compare::<Day5, _>(0..200, 8, reference, differential::solve::<Day5>)?;
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trip_test(seeds: Vec<u64>, maps in vec(range_map(), 0..4)) {
+            for map in &maps {
+                prop_assert_eq!(map, &map.to_string().parse::<RangeMap>()?);
+            }
+            let almanac = Almanac { seeds, maps };
+            prop_assert_eq!(&almanac, &almanac.to_string().parse::<Almanac>()?);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() -> Result<()> {
+        let almanac: Almanac = include_str!("test.txt").parse()?;
+        let json = serde_json::to_string(&almanac)?;
+        assert_eq!(almanac, serde_json::from_str(&json)?);
+        // Pairs are sorted again, while overlapping ones are rejected
+        let map = r#"{"name":"a-to-b","maps":[
+            {"source":[10,5],"destination":[0,5]},{"source":[0,5],"destination":[20,5]}]}"#;
+        let map: RangeMap = serde_json::from_str(map)?;
+        assert_eq!(
+            vec![0, 10],
+            map.maps.iter().map(|p| p.source.0).collect::<Vec<_>>()
+        );
+        let map = r#"{"name":"a-to-b","maps":[
+            {"source":[0,5],"destination":[0,5]},{"source":[4,5],"destination":[20,5]}]}"#;
+        let error = serde_json::from_str::<RangeMap>(map).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("a-to-b map: range at 4 overlaps"));
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use crate::RaceInt;
+
+/// Describes how far a boat travels in a race given how long its button was held.
+///
+/// The distance curve over hold times must strictly increase up to its peak and never
+/// increase after it, which is what lets [`winning_interval`] binary search it. Returning
+/// `None` means the distance does not fit in `T` and is treated as larger than any record.
+pub trait BoatModel<T> {
+    fn distance(&self, time: &T, hold: &T) -> Option<T>;
+}
+
+/// The puzzle's rules: every millisecond held adds one unit of speed
+pub struct Linear;
+
+impl<T: RaceInt> BoatModel<T> for Linear {
+    fn distance(&self, time: &T, hold: &T) -> Option<T> {
+        time.checked_sub(hold)?.checked_mul(hold)
+    }
+}
+
+/// Speed grows by `acceleration` per millisecond held but never exceeds `top_speed`
+pub struct TopSpeed<T> {
+    pub acceleration: T,
+    pub top_speed: T,
+}
+
+impl<T: RaceInt> BoatModel<T> for TopSpeed<T> {
+    fn distance(&self, time: &T, hold: &T) -> Option<T> {
+        // Once the top speed is reached holding longer only loses time, so an overflowing
+        // speed can safely be clamped as well
+        let speed = hold
+            .checked_mul(&self.acceleration)
+            .map_or(self.top_speed.clone(), |speed| {
+                speed.min(self.top_speed.clone())
+            });
+        time.checked_sub(hold)?.checked_mul(&speed)
+    }
+}
+
+/// The boat needs `penalty` milliseconds after release before it starts moving
+pub struct HoldPenalty<T> {
+    pub penalty: T,
+}
+
+impl<T: RaceInt> BoatModel<T> for HoldPenalty<T> {
+    fn distance(&self, time: &T, hold: &T) -> Option<T> {
+        let moving = time
+            .checked_sub(hold)?
+            .checked_sub(&self.penalty)
+            .unwrap_or_else(T::zero);
+        moving.checked_mul(hold)
+    }
+}
+
+// Compares two distances where `None` stands for a distance too large to represent
+fn exceeds<T: RaceInt>(distance: &Option<T>, other: &Option<T>) -> bool {
+    match (distance, other) {
+        (Some(distance), Some(other)) => distance > other,
+        (None, Some(_)) => true,
+        (_, None) => false,
+    }
+}
+
+// Returns the first value in low..=high for which the predicate holds, if any. The predicate
+// has to be false for some prefix of the range and true for the rest.
+fn partition_point<T: RaceInt>(
+    mut low: T,
+    mut high: T,
+    predicate: impl Fn(&T) -> bool,
+) -> Option<T> {
+    if !predicate(&high) {
+        return None;
+    }
+    let two = T::one() + T::one();
+    while low < high {
+        let mid = low.clone() + (high.clone() - low.clone()) / two.clone();
+        if predicate(&mid) {
+            high = mid;
+        } else {
+            low = mid + T::one();
+        }
+    }
+    Some(low)
+}
+
+/// Finds the inclusive range of hold times in `0..=time` whose distance beats `record`.
+///
+/// The peak of the curve is located first by searching for where the distance stops
+/// increasing, then the first and last winning holds are searched for on either side of it.
+pub fn winning_interval<T: RaceInt>(
+    model: &impl BoatModel<T>,
+    time: &T,
+    record: &T,
+) -> Option<(T, T)> {
+    let distance = |hold: &T| model.distance(time, hold);
+    let record = Some(record.clone());
+    // The last hold time always satisfies this, so a peak is always found
+    let peak = partition_point(T::zero(), time.clone(), |hold| {
+        *hold == *time || !exceeds(&distance(&(hold.clone() + T::one())), &distance(hold))
+    })?;
+    if !exceeds(&distance(&peak), &record) {
+        return None;
+    }
+    let first = partition_point(T::zero(), peak.clone(), |hold| {
+        exceeds(&distance(hold), &record)
+    })?;
+    let last = match partition_point(peak, time.clone(), |hold| {
+        !exceeds(&distance(hold), &record)
+    }) {
+        Some(after_last) => after_last - T::one(),
+        None => time.clone(),
+    };
+    Some((first, last))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::boat::{winning_interval, BoatModel, HoldPenalty, Linear, TopSpeed};
+
+    // Tries every hold time, the way the puzzle was originally solved
+    fn brute_force(model: &impl BoatModel<u64>, time: u64, record: u64) -> Option<(u64, u64)> {
+        let winners: Vec<u64> = (0..=time)
+            .filter(|hold| model.distance(&time, hold).unwrap() > record)
+            .collect();
+        Some((*winners.first()?, *winners.last()?))
+    }
+
+    fn check(model: &impl BoatModel<u64>) {
+        for time in 0..40 {
+            for record in 0..120 {
+                assert_eq!(
+                    brute_force(model, time, record),
+                    winning_interval(model, &time, &record),
+                    "time: {time}, record: {record}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn linear_test() {
+        check(&Linear);
+        assert_eq!(Some((2, 5)), winning_interval(&Linear, &7u64, &9));
+    }
+
+    #[test]
+    fn top_speed_test() {
+        for acceleration in 1..4 {
+            for top_speed in 1..12 {
+                check(&TopSpeed {
+                    acceleration,
+                    top_speed,
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn hold_penalty_test() {
+        for penalty in 0..6 {
+            check(&HoldPenalty { penalty });
+        }
+    }
+
+    #[test]
+    fn overflow_test() {
+        // The peak distance of this race is far beyond a u64, so the overflowing
+        // middle of the curve must still count as winning
+        let time = u64::MAX;
+        let record = u64::MAX - 1;
+        let (first, last) = winning_interval(&Linear, &time, &record).unwrap();
+        assert_eq!((2, u64::MAX - 2), (first, last));
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    error::Error as StdError,
+    fmt::{self, Display, Formatter},
+};
+
+use common::Diagnostic;
+
+/// Why a race leaderboard could not be solved
+#[derive(Debug)]
+pub enum Error {
+    /// The leaderboard is not a `Time:` line followed by a `Distance:` line
+    Malformed(Diagnostic),
+    /// A time or distance is not a number, or too large for the integer type
+    InvalidNumber(Diagnostic),
+    /// A single race was expected, but the leaderboard has some other number of columns
+    NotASingleRace(Diagnostic),
+    /// The product of the ways to win each race does not fit in the integer type
+    MarginOverflow,
+    /// The race read with the columns joined together does not fit in the integer type
+    MergedRaceOverflow,
+}
+
+impl Error {
+    /// Where in the input the error is, for errors that point at a line
+    pub fn diagnostic(&self) -> Option<&Diagnostic> {
+        match self {
+            Error::Malformed(diagnostic)
+            | Error::InvalidNumber(diagnostic)
+            | Error::NotASingleRace(diagnostic) => Some(diagnostic),
+            Error::MarginOverflow | Error::MergedRaceOverflow => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Malformed(diagnostic)
+            | Error::InvalidNumber(diagnostic)
+            | Error::NotASingleRace(diagnostic) => write!(f, "{diagnostic}"),
+            Error::MarginOverflow => {
+                write!(f, "margin of error does not fit in the integer type")
+            }
+            Error::MergedRaceOverflow => {
+                write!(f, "merged race does not fit in the integer type")
+            }
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        // The diagnostic is what gets displayed, so its source comes next
+        self.diagnostic().and_then(StdError::source)
+    }
+}
+
+// Errors found by the parsers are about numbers when a number failed to parse, and otherwise
+// about the layout of the leaderboard
+impl From<Diagnostic> for Error {
+    fn from(diagnostic: Diagnostic) -> Self {
+        match diagnostic.source {
+            Some(_) => Error::InvalidNumber(diagnostic),
+            None => Error::Malformed(diagnostic),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use common::generate::{Generator, Rng};
+
+use crate::{Day6, Leaderboard, Race};
+
+// Races last 7 to 99 milliseconds and every record can be beaten. Part 2 joins all columns into
+// one race, which only fits in a u128 for around a dozen races.
+impl Generator for Day6 {
+    fn generate(size: usize, seed: u64) -> String {
+        let mut rng = Rng::new(seed);
+        let races = (0..size.max(1))
+            .map(|_| {
+                let time = rng.range(7..100);
+                let hold = rng.range(0..time / 2);
+                Race {
+                    time,
+                    best_distance: hold * (time - hold),
+                }
+            })
+            .collect();
+        format!("{}\n", Leaderboard::new(races))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use common::{generate::Generator, Solution};
+
+    use crate::{Day6, Leaderboard};
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = Day6::generate(300, 1);
+        assert_eq!(input, Day6::generate(300, 1));
+        assert_ne!(input, Day6::generate(300, 2));
+        let leaderboard: Leaderboard<u64> = input.parse()?;
+        assert_eq!(300, leaderboard.races.len());
+        assert!(leaderboard
+            .races
+            .iter()
+            .all(|race| race.count_winning_speeds() > 0));
+        let leaderboard = Day6::parse(&Day6::generate(4, 1))?;
+        assert!(Day6::part_2(&leaderboard)? > 0u8.into());
+        Ok(())
+    }
+}
@@ -0,0 +1,578 @@
+use std::{
+    error::Error as StdError,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use common::{
+    input::normalize,
+    parse::{self, integers, parse_all, rest_of_line, IResult},
+    Diagnostic, Solution,
+};
+use nom::{character::complete::line_ending, combinator::opt, error::context};
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, Unsigned};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::boat::{BoatModel, Linear};
+
+pub mod boat;
+mod error;
+mod generate;
+
+pub use error::Error;
+
+/// Integer types a race can be solved with, from fixed width primitives up to big integers
+pub trait RaceInt:
+    Unsigned
+    + CheckedAdd
+    + CheckedSub
+    + CheckedMul
+    + Clone
+    + Ord
+    + Display
+    + FromStr<Err: StdError + Send + Sync + 'static>
+{
+}
+
+impl RaceInt for u64 {}
+
+impl RaceInt for u128 {}
+
+#[cfg(feature = "bigint")]
+impl RaceInt for num_bigint::BigUint {}
+
+// Big integers are only needed when the concatenated part 2 race overflows a u128
+#[cfg(feature = "bigint")]
+pub type Int = num_bigint::BigUint;
+#[cfg(not(feature = "bigint"))]
+pub type Int = u128;
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Race<T> {
+    pub time: T,
+    pub best_distance: T,
+}
+
+impl<T: RaceInt> Race<T> {
+    /// Inclusive range of button hold times that beat the record under the given model
+    pub fn winning_holds(&self, model: &impl BoatModel<T>) -> Option<(T, T)> {
+        boat::winning_interval(model, &self.time, &self.best_distance)
+    }
+
+    pub fn count_winning_speeds(&self) -> T {
+        self.count_winning_holds(&Linear)
+    }
+
+    pub fn count_winning_holds(&self, model: &impl BoatModel<T>) -> T {
+        self.winning_holds(model)
+            .map_or(T::zero(), |(first, last)| last - first + T::one())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Leaderboard<T> {
+    races: Vec<Race<T>>,
+    // How many zeros the time and distance column of each race were padded with. They do not
+    // change the races, but are digits like any other once the columns are merged.
+    leading_zeros: Vec<(usize, usize)>,
+}
+
+impl<T: RaceInt> Leaderboard<T> {
+    /// A leaderboard whose columns are written without leading zeros
+    pub fn new(races: Vec<Race<T>>) -> Self {
+        let leading_zeros = vec![(0, 0); races.len()];
+        Leaderboard {
+            races,
+            leading_zeros,
+        }
+    }
+
+    pub fn races(&self) -> &[Race<T>] {
+        &self.races
+    }
+
+    // The time and distance column of each race as they were written
+    fn columns(&self) -> impl Iterator<Item = (String, String)> + '_ {
+        self.races.iter().enumerate().map(|(i, race)| {
+            // A deserialized leaderboard may not record padding for every race
+            let (time_zeros, distance_zeros) =
+                self.leading_zeros.get(i).copied().unwrap_or_default();
+            (
+                "0".repeat(time_zeros) + &race.time.to_string(),
+                "0".repeat(distance_zeros) + &race.best_distance.to_string(),
+            )
+        })
+    }
+
+    /// The product of the number of ways to win each race, if it fits in `T`
+    pub fn margin_of_error(&self) -> Option<T> {
+        self.races.iter().try_fold(T::one(), |acc, race| {
+            acc.checked_mul(&race.count_winning_speeds())
+        })
+    }
+
+    /// Reads the leaderboard the way part 2 intends, ignoring the spaces between columns so
+    /// that all times and all distances become one race. Columns are joined digit for digit as
+    /// they were written, so `7 05` becomes 705.
+    pub fn kerning_merged(&self) -> Result<Race<T>, Error> {
+        let (time, best_distance): (String, String) = self.columns().unzip();
+        // The columns are all digits, so the only way to fail is by being too large
+        let parse = |value: String| value.parse().map_err(|_| Error::MergedRaceOverflow);
+        Ok(Race {
+            time: parse(time)?,
+            best_distance: parse(best_distance)?,
+        })
+    }
+}
+
+// Parses a "Label: 1 2 3" line, checking that the label is the expected one. The whole line is
+// returned alongside the values so that column counts can be reported against it.
+fn labeled_line<'a, T: RaceInt>(
+    label: &'static str,
+) -> impl FnMut(&'a str) -> IResult<'a, (&'a str, Vec<T>)> {
+    move |input| {
+        let line = rest_of_line(input);
+        if input.is_empty() {
+            return parse::Error::failure(
+                input,
+                "leaderboard should have exactly two lines",
+                format!("expected a {label} line"),
+            );
+        }
+        let Some((found, _)) = line.split_once(':') else {
+            return parse::Error::failure(
+                line,
+                "line is missing a label",
+                format!("expected \"{label}:\""),
+            );
+        };
+        if found != label {
+            return parse::Error::failure(
+                found,
+                format!("line should be labeled {label}"),
+                format!("expected \"{label}\""),
+            );
+        }
+        let values = &input[found.len() + 1..];
+        let (rest, values) = context("number is not valid", integers)(values)?;
+        Ok((rest, (line, values)))
+    }
+}
+
+// Parses an LF or CRLF line ending, rejecting carriage returns anywhere else
+fn line_end(input: &str) -> IResult<'_, &str> {
+    if input.starts_with('\r') && !input.starts_with("\r\n") {
+        return parse::Error::failure(
+            &input[..1],
+            "line contains a stray carriage return",
+            "unexpected '\\r'",
+        );
+    }
+    line_ending(input)
+}
+
+fn leaderboard<T: RaceInt>(input: &str) -> IResult<'_, Leaderboard<T>> {
+    let (rest, (time_line, times)) = labeled_line::<T>("Time")(input)?;
+    let (rest, first_ending) = opt(line_end)(rest)?;
+    let (rest, (distance_line, distances)) = labeled_line::<T>("Distance")(rest)?;
+    let (rest, last_ending) = opt(line_end)(rest)?;
+    // Line endings are accepted in either style as long as they are not mixed
+    if let (Some(first), Some(last)) = (first_ending, last_ending) {
+        if first != last {
+            return parse::Error::failure(
+                &last[..0],
+                "line ends with a different line ending than line 1",
+                "mixed line endings",
+            );
+        }
+    }
+    if !rest.is_empty() {
+        return parse::Error::failure(
+            rest_of_line(rest),
+            "leaderboard should have exactly two lines",
+            "unexpected line",
+        );
+    }
+    if times.is_empty() {
+        return parse::Error::failure(
+            time_line,
+            "leaderboard does not contain any races",
+            "expected at least one time",
+        );
+    }
+    if times.len() != distances.len() {
+        return parse::Error::failure(
+            distance_line,
+            format!(
+                "leaderboard has {} times but {} distances",
+                times.len(),
+                distances.len()
+            ),
+            "column counts differ",
+        );
+    }
+    let races: Vec<Race<T>> = times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, best_distance)| Race {
+            time,
+            best_distance,
+        })
+        .collect();
+    let leading_zeros = leading_zeros(time_line)
+        .into_iter()
+        .zip(leading_zeros(distance_line))
+        .collect();
+    let leaderboard = Leaderboard {
+        races,
+        leading_zeros,
+    };
+    Ok((rest, leaderboard))
+}
+
+// Counts the zeros each column of a labeled line starts with, where a column of only zeros
+// keeps one of them as its value
+fn leading_zeros(line: &str) -> Vec<usize> {
+    let (_, columns) = line.split_once(':').unwrap_or_default();
+    columns
+        .split_ascii_whitespace()
+        .map(|column| column.len() - column.trim_start_matches('0').len().max(1))
+        .collect()
+}
+
+impl<T: RaceInt> FromStr for Leaderboard<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(parse_all(s, leaderboard)?)
+    }
+}
+
+/// Parses a leaderboard holding exactly one race
+impl<T: RaceInt> FromStr for Race<T> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut leaderboard = parse_all(s, leaderboard)?;
+        if leaderboard.races.len() != 1 {
+            return Err(Error::NotASingleRace(Diagnostic::new(
+                s,
+                rest_of_line(s),
+                format!("race leaderboard has {} columns", leaderboard.races.len()),
+                "expected a single time",
+            )));
+        }
+        Ok(leaderboard.races.remove(0))
+    }
+}
+
+// Writes the two lines of a leaderboard with every column right aligned, as in the puzzle
+fn write_columns(
+    f: &mut Formatter<'_>,
+    columns: impl IntoIterator<Item = (String, String)>,
+) -> fmt::Result {
+    let columns: Vec<(String, String)> = columns.into_iter().collect();
+    write!(f, "Time:    ")?;
+    for (time, distance) in &columns {
+        write!(f, "  {time:>0$}", distance.len())?;
+    }
+    write!(f, "\nDistance:")?;
+    for (time, distance) in &columns {
+        write!(f, "  {distance:>0$}", time.len())?;
+    }
+    Ok(())
+}
+
+impl<T: RaceInt> Display for Race<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_columns(f, [(self.time.to_string(), self.best_distance.to_string())])
+    }
+}
+
+impl<T: RaceInt> Display for Leaderboard<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Columns keep their leading zeros, which are digits of the part 2 race
+        write_columns(f, self.columns())
+    }
+}
+
+fn margin_of_error<T: RaceInt>(leaderboard: &Leaderboard<T>) -> Result<T, Error> {
+    leaderboard.margin_of_error().ok_or(Error::MarginOverflow)
+}
+
+pub fn calculate_part_1<T: RaceInt>(input: &str) -> Result<T, Error> {
+    let leaderboard: Leaderboard<T> = normalize(input).parse()?;
+    margin_of_error(&leaderboard)
+}
+
+pub fn calculate_part_2<T: RaceInt>(input: &str) -> Result<T, Error> {
+    let leaderboard: Leaderboard<T> = normalize(input).parse()?;
+    let race = leaderboard.kerning_merged()?;
+    let answer = race.count_winning_speeds();
+    Ok(answer)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Model = Leaderboard<Int>;
+    type Answer = Int;
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Model, Error> {
+        normalize(input).parse()
+    }
+
+    fn part_1(leaderboard: &Self::Model) -> Result<Int, Error> {
+        margin_of_error(leaderboard)
+    }
+
+    fn part_2(leaderboard: &Self::Model) -> Result<Int, Error> {
+        Ok(leaderboard.kerning_merged()?.count_winning_speeds())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::Result;
+    use common::{differential, input::windows, Solution};
+    use proptest::{collection::vec, prelude::*};
+
+    use crate::{calculate_part_1, calculate_part_2, Day6, Error, Leaderboard, Race};
+
+    common::examples_test!(Day6);
+
+    fn race() -> impl Strategy<Value = Race<u64>> {
+        (any::<u64>(), any::<u64>()).prop_map(|(time, best_distance)| Race {
+            time,
+            best_distance,
+        })
+    }
+
+    // Time 10^20 with a record set by holding for 10^9, so every speed strictly between
+    // 10^9 and 10^20 - 10^9 wins
+    const OVERFLOW_INPUT: &str =
+        "Time:      1000000000 00000000000\nDistance:  99999999999 000000000000000000\n";
+    const OVERFLOW_ANSWER: &str = "99999999997999999999";
+
+    #[test]
+    fn calculate_part_1_test() -> Result<()> {
+        let input = include_str!("test.txt");
+        assert_eq!(288u64, calculate_part_1(input)?);
+        Ok(())
+    }
+    #[test]
+    fn calculate_part_2_test() -> Result<()> {
+        let input = include_str!("test.txt");
+        assert_eq!(71503u64, calculate_part_2(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn windows_input_test() -> Result<()> {
+        let input = windows(include_str!("test.txt"));
+        assert_eq!(288u64, calculate_part_1(&input)?);
+        assert_eq!(71503u64, calculate_part_2(&input)?);
+        // Line endings that differ are only rejected when parsing without normalizing first
+        let mixed = "Time: 7 15\r\nDistance: 9 40\n\n";
+        assert!(mixed.parse::<Leaderboard<u64>>().is_err());
+        let leaderboard = Day6::parse(mixed)?;
+        assert_eq!("32", Day6::part_1(&leaderboard)?.to_string());
+        Ok(())
+    }
+    #[test]
+    fn calculate_part_2_u128_test() -> Result<()> {
+        // Every column fits in a u64, only the merged record does not
+        assert!(matches!(
+            calculate_part_2::<u64>(OVERFLOW_INPUT),
+            Err(Error::MergedRaceOverflow)
+        ));
+        assert_eq!(
+            OVERFLOW_ANSWER.parse::<u128>()?,
+            calculate_part_2(OVERFLOW_INPUT)?
+        );
+        Ok(())
+    }
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn calculate_part_2_bigint_test() -> Result<()> {
+        use num_bigint::BigUint;
+
+        assert_eq!(
+            OVERFLOW_ANSWER.parse::<BigUint>()?,
+            calculate_part_2(OVERFLOW_INPUT)?
+        );
+        // Same construction scaled up to time 10^60 and hold 10^29, far beyond a u128
+        let time = "1".to_string() + &"0".repeat(60);
+        let record = format!("{}{}", "9".repeat(31), "0".repeat(58));
+        let input = format!(
+            "Time: {} {}\nDistance: {} {}\n",
+            &time[..30],
+            &time[30..],
+            &record[..31],
+            &record[31..]
+        );
+        assert!(calculate_part_2::<u128>(&input).is_err());
+        let expected =
+            BigUint::from(10u8).pow(60) - BigUint::from(2u8) * BigUint::from(10u8).pow(29) - 1u8;
+        assert_eq!(expected, calculate_part_2(&input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn margin_of_error_overflow_test() {
+        // Multiplying the ways to win used to overflow
+        let input = format!(
+            "Time: {}\nDistance: {}\n",
+            "100 ".repeat(12),
+            "0 ".repeat(12)
+        );
+        let error = calculate_part_1::<u64>(&input).unwrap_err();
+        assert!(matches!(error, Error::MarginOverflow));
+        assert_eq!(
+            "margin of error does not fit in the integer type",
+            error.to_string()
+        );
+        assert!(calculate_part_1::<u128>(&input).is_ok());
+    }
+
+    #[test]
+    fn leaderboard_errors_test() {
+        let cases = [
+            (
+                "Time: 7\n",
+                "leaderboard should have exactly two lines",
+                (2, 0..0),
+            ),
+            (
+                "Time: 7\nDistance: 9\nTime: 8\n",
+                "leaderboard should have exactly two lines",
+                (3, 0..7),
+            ),
+            (
+                "Time: 7\nDistance: 9\n\n",
+                "leaderboard should have exactly two lines",
+                (3, 0..0),
+            ),
+            (
+                "Times: 7\nDistance: 9\n",
+                "line should be labeled Time",
+                (1, 0..5),
+            ),
+            ("Time: 7\n9\n", "line is missing a label", (2, 0..1)),
+            (
+                "Time: 7 15\nDistance: 9\n",
+                "leaderboard has 2 times but 1 distances",
+                (2, 0..11),
+            ),
+            (
+                "Time:\nDistance:\n",
+                "leaderboard does not contain any races",
+                (1, 0..5),
+            ),
+            (
+                "Time: 7\r\nDistance: 9\n",
+                "line ends with a different line ending than line 1",
+                (2, 11..11),
+            ),
+            (
+                "Time: 7\rDistance: 9\n",
+                "line contains a stray carriage return",
+                (1, 7..8),
+            ),
+            (
+                "Time: 7\nDistance: 9x\n",
+                "number is not valid",
+                (2, 10..12),
+            ),
+        ];
+        for (input, message, position) in cases {
+            let error = input.parse::<Leaderboard<u64>>().err().unwrap();
+            let error = error.diagnostic().unwrap();
+            assert_eq!(message, error.message, "input: {input:?}");
+            assert_eq!(
+                position,
+                (error.line_number, error.span.clone()),
+                "input: {input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn kerning_merged_test() -> Result<()> {
+        let input = include_str!("test.txt").replace('\n', "\r\n");
+        let leaderboard: Leaderboard<u64> = input.parse()?;
+        assert_eq!(3, leaderboard.races().len());
+        let race = leaderboard.kerning_merged()?;
+        assert_eq!((71530, 940200), (race.time, race.best_distance));
+        // Zeros at the start of a column are digits like any other once columns are merged
+        let leaderboard: Leaderboard<u64> = "Time:  7 05 00\nDistance:  9 040 0\n".parse()?;
+        assert_eq!(5, leaderboard.races()[1].time);
+        let race = leaderboard.kerning_merged()?;
+        assert_eq!((70500, 90400), (race.time, race.best_distance));
+        assert_eq!(
+            "Time:      7   05  00\nDistance:  9  040   0",
+            leaderboard.to_string()
+        );
+        assert_eq!(leaderboard, leaderboard.to_string().parse()?);
+        Ok(())
+    }
+
+    #[test]
+    fn display_test() -> Result<()> {
+        let input = include_str!("test.txt");
+        let leaderboard: Leaderboard<u64> = input.parse()?;
+        assert_eq!(input.trim_end(), leaderboard.to_string());
+        let error = input.parse::<Race<u64>>();
+        let Err(Error::NotASingleRace(error)) = error else {
+            panic!("expected more than one race, got {error:?}");
+        };
+        assert_eq!("race leaderboard has 3 columns", error.message);
+        let race = leaderboard.kerning_merged()?;
+        assert_eq!("Time:       71530\nDistance:  940200", race.to_string());
+        Ok(())
+    }
+
+    // Tries every hold time of every race
+    fn reference(input: &str) -> Result<(String, String), String> {
+        let lines: Vec<Vec<&str>> = input
+            .lines()
+            .map(|line| line.split_whitespace().skip(1).collect())
+            .collect();
+        let ways = |time: &str, record: &str| -> Result<u64, String> {
+            let time: u64 = time.parse().map_err(|e| format!("{e}"))?;
+            let record: u64 = record.parse().map_err(|e| format!("{e}"))?;
+            Ok((0..=time)
+                .filter(|hold| hold * (time - hold) > record)
+                .count() as u64)
+        };
+        let part_1 = lines[0]
+            .iter()
+            .zip(&lines[1])
+            .map(|(time, record)| ways(time, record))
+            .product::<Result<u64, String>>()?;
+        let part_2 = ways(&lines[0].concat(), &lines[1].concat())?;
+        Ok((part_1.to_string(), part_2.to_string()))
+    }
+
+    #[test]
+    fn differential_test() -> Result<()> {
+        // Joined races are tried hold by hold, so they have to stay short
+        differential::compare::<Day6, _>(0..300, 3, reference, differential::solve::<Day6>)?;
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trip_test(race in race(), races in vec(race(), 1..8)) {
+            prop_assert_eq!(&race, &race.to_string().parse::<Race<u64>>()?);
+            let leaderboard = Leaderboard::new(races);
+            prop_assert_eq!(&leaderboard, &leaderboard.to_string().parse::<Leaderboard<u64>>()?);
+        }
+    }
+}
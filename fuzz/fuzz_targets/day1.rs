@@ -0,0 +1,13 @@
+#![no_main]
+
+use common::Solution;
+use day1::{solve, Day1};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(model) = Day1::parse(input) {
+        let _ = Day1::part_1(&model);
+        let _ = Day1::part_2(&model);
+    }
+    let _ = solve(input.as_bytes());
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use common::Solution;
+use day2::{solve, Day2};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(model) = Day2::parse(input) {
+        let _ = Day2::part_1(&model);
+        let _ = Day2::part_2(&model);
+    }
+    let _ = solve(input.as_bytes());
+});
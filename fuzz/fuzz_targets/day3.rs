@@ -0,0 +1,12 @@
+#![no_main]
+
+use common::Solution;
+use day3::Day3;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(model) = Day3::parse(input) {
+        let _ = Day3::part_1(&model);
+        let _ = Day3::part_2(&model);
+    }
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use common::Solution;
+use day4::{solve, Day4};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(model) = Day4::parse(input) {
+        let _ = Day4::part_1(&model);
+        let _ = Day4::part_2(&model);
+    }
+    let _ = solve(input.as_bytes());
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+use common::Solution;
+use day5::Day5;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(model) = Day5::parse(input) {
+        let _ = Day5::part_1(&model);
+        // Part 2 tries every seed of every range, which takes too long for arbitrary lengths
+        let seeds = model.seeds.chunks(2).filter_map(|pair| pair.get(1));
+        if seeds.fold(0u64, |total, length| total.saturating_add(*length)) < 100_000 {
+            let _ = Day5::part_2(&model);
+        }
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use common::Solution;
+use day6::Day6;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(model) = Day6::parse(input) {
+        let _ = Day6::part_1(&model);
+        let _ = Day6::part_2(&model);
+    }
+});